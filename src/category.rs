@@ -1,4 +1,9 @@
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, enum_iterator::Sequence,
+)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum Category {
     ActiveSearchMatch,
@@ -33,7 +38,10 @@ pub enum Category {
     Whitespace,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, enum_iterator::Sequence,
+)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum Token {
     Attribute,
@@ -68,10 +76,60 @@ pub enum Token {
     Variant,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, enum_iterator::Sequence,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum DiagnosticLevel {
     Error,
     Warning,
     Info,
     Hint,
 }
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, enum_iterator::Sequence,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl AnsiColor {
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn normal(self) -> Self {
+        match self {
+            Self::BrightBlack => Self::Black,
+            Self::BrightRed => Self::Red,
+            Self::BrightGreen => Self::Green,
+            Self::BrightYellow => Self::Yellow,
+            Self::BrightBlue => Self::Blue,
+            Self::BrightMagenta => Self::Magenta,
+            Self::BrightCyan => Self::Cyan,
+            Self::BrightWhite => Self::White,
+            _ => self,
+        }
+    }
+
+    pub fn is_bright(self) -> bool {
+        self.normal() != self
+    }
+}
@@ -1,4 +1,8 @@
-use palette::{Darken, Lighten, Srgb};
+use palette::{
+    convert::FromColorUnclamped, Darken, IsWithinBounds, Lighten, Mix, Oklab, Oklch, ShiftHue, Srgb,
+};
+use serde::{de, Deserialize, Deserializer};
+use std::fmt::{self, Display};
 
 pub fn lighten(c: Srgb<u8>, factor: f32) -> Srgb<u8> {
     Srgb::from_linear(c.into_linear().lighten(factor))
@@ -7,3 +11,189 @@ pub fn lighten(c: Srgb<u8>, factor: f32) -> Srgb<u8> {
 pub fn darken(c: Srgb<u8>, factor: f32) -> Srgb<u8> {
     Srgb::from_linear(c.into_linear().darken(factor))
 }
+
+pub fn to_oklch(c: Srgb<u8>) -> Oklch {
+    Oklch::from_color_unclamped(c.into_format::<f32>())
+}
+
+pub fn from_oklch(c: Oklch) -> Srgb<u8> {
+    gamut_map(c)
+}
+
+pub fn adjust_lightness(c: Srgb<u8>, delta: f32) -> Srgb<u8> {
+    let c = to_oklch(c);
+    from_oklch(Oklch {
+        l: c.l + delta,
+        ..c
+    })
+}
+
+pub fn with_lightness(c: Srgb<u8>, lightness: f32) -> Srgb<u8> {
+    from_oklch(Oklch {
+        l: lightness,
+        ..to_oklch(c)
+    })
+}
+
+pub fn scale_chroma(c: Srgb<u8>, factor: f32) -> Srgb<u8> {
+    let c = to_oklch(c);
+    from_oklch(Oklch {
+        chroma: c.chroma * factor,
+        ..c
+    })
+}
+
+pub fn rotate_hue(c: Srgb<u8>, degrees: f32) -> Srgb<u8> {
+    from_oklch(to_oklch(c).shift_hue(degrees))
+}
+
+pub fn mix(a: Srgb<u8>, b: Srgb<u8>, factor: f32) -> Srgb<u8> {
+    let a = Oklab::from_color_unclamped(a.into_format::<f32>());
+    let b = Oklab::from_color_unclamped(b.into_format::<f32>());
+    from_oklch(Oklch::from_color_unclamped(a.mix(b, factor)))
+}
+
+pub fn blend(foreground: Srgb<u8>, alpha: f32, background: Srgb<u8>) -> Srgb<u8> {
+    mix(background, foreground, alpha)
+}
+
+// Reduces chroma while preserving lightness and hue until the color fits in sRGB.
+pub fn gamut_map(c: Oklch) -> Srgb<u8> {
+    let l = c.l.clamp(0.0, 1.0);
+    let to_srgb = |chroma| Srgb::<f32>::from_color_unclamped(Oklch { l, chroma, ..c });
+    let candidate = to_srgb(c.chroma);
+    if candidate.is_within_bounds() {
+        return candidate.into_format();
+    }
+    let (mut low, mut high) = (0.0, c.chroma.max(0.0));
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if to_srgb(mid).is_within_bounds() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let Srgb {
+        red, green, blue, ..
+    } = to_srgb(low);
+    Srgb::new(
+        red.clamp(0.0, 1.0),
+        green.clamp(0.0, 1.0),
+        blue.clamp(0.0, 1.0),
+    )
+    .into_format()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CssColor(pub(crate) Srgb<u8>);
+
+impl Display for CssColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgb {
+            red, green, blue, ..
+        } = self.0;
+        write!(f, "#{red:02x}{green:02x}{blue:02x}")
+    }
+}
+
+pub fn parse_color(s: &str) -> Result<Srgb<u8>, ParseColorError> {
+    let s = s.trim();
+    let color = if s.starts_with('#') {
+        s.parse().ok()
+    } else {
+        palette::named::from_str(&s.to_lowercase())
+    };
+    color.ok_or_else(|| ParseColorError(s.into()))
+}
+
+pub(crate) fn deserialize_color<'de, D>(deserializer: D) -> Result<Srgb<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_color(&s).map_err(de::Error::custom)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Invalid color `{0}`; expected `#rrggbb` or a CSS color name")]
+pub struct ParseColorError(String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors_are_parsed() {
+        assert_eq!(parse_color("#1a2B3c"), Ok(Srgb::new(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_color("  #ffffff "), Ok(Srgb::new(0xff, 0xff, 0xff)));
+    }
+
+    #[test]
+    fn css_color_names_are_parsed_case_insensitively() {
+        assert_eq!(parse_color("SteelBlue"), Ok(Srgb::new(0x46, 0x82, 0xb4)));
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        for s in ["#12345", "#gggggg", "notacolor", ""] {
+            assert!(parse_color(s).is_err(), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn css_color_is_lowercase_hex() {
+        assert_eq!(CssColor(Srgb::new(0xab, 0x01, 0xff)).to_string(), "#ab01ff");
+    }
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn oklch_matches_reference_values() {
+        let red = to_oklch(Srgb::new(0xff, 0x00, 0x00));
+        assert_close(red.l, 0.628, 1e-3);
+        assert_close(red.chroma, 0.2577, 1e-3);
+        assert_close(red.hue.into_positive_degrees(), 29.23, 0.1);
+        let white = to_oklch(Srgb::new(0xff, 0xff, 0xff));
+        assert_close(white.l, 1.0, 1e-3);
+        assert_close(white.chroma, 0.0, 1e-3);
+    }
+
+    #[test]
+    fn oklch_round_trips() {
+        for rgb in [0x000000, 0xffffff, 0x4682b4, 0xdc143c, 0x2e8b57, 0x123456] {
+            let c = Srgb::from(rgb);
+            assert_eq!(from_oklch(to_oklch(c)), c, "{rgb:06x}");
+        }
+    }
+
+    #[test]
+    fn gamut_map_keeps_lightness_and_hue() {
+        let c = Oklch::new(0.7, 0.4, 150.0);
+        let mapped = to_oklch(gamut_map(c));
+        assert_close(mapped.l, 0.7, 0.01);
+        assert_close(mapped.hue.into_positive_degrees(), 150.0, 2.0);
+        assert!(mapped.chroma < 0.4);
+        assert_eq!(
+            gamut_map(Oklch::new(1.5, 0.1, 40.0)),
+            Srgb::new(255, 255, 255)
+        );
+        assert_eq!(gamut_map(Oklch::new(-0.5, 0.1, 40.0)), Srgb::new(0, 0, 0));
+    }
+
+    #[test]
+    fn lightness_is_adjusted_in_oklch() {
+        let c = Srgb::new(0x46, 0x82, 0xb4);
+        assert_close(
+            to_oklch(adjust_lightness(c, 0.1)).l,
+            to_oklch(c).l + 0.1,
+            0.01,
+        );
+        assert_close(to_oklch(with_lightness(c, 0.3)).l, 0.3, 0.01);
+    }
+}
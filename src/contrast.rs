@@ -0,0 +1,271 @@
+use crate::{from_oklch, to_oklch, Category, ColorEntry, DiagnosticLevel, Theme, Token};
+use palette::{Oklch, Srgb};
+
+pub fn wcag_contrast_ratio(foreground: Srgb<u8>, background: Srgb<u8>) -> f32 {
+    let a = relative_luminance(foreground);
+    let b = relative_luminance(background);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+fn relative_luminance(c: Srgb<u8>) -> f32 {
+    let c = c.into_format::<f32>().into_linear();
+    0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+}
+
+// APCA-W3 0.0.98G-4g
+pub fn apca_contrast(foreground: Srgb<u8>, background: Srgb<u8>) -> f32 {
+    const NORM_BG: f32 = 0.56;
+    const NORM_TEXT: f32 = 0.57;
+    const REV_TEXT: f32 = 0.62;
+    const REV_BG: f32 = 0.65;
+    const SCALE: f32 = 1.14;
+    const LOW_OFFSET: f32 = 0.027;
+    const LOW_CLIP: f32 = 0.1;
+    const DELTA_Y_MIN: f32 = 0.0005;
+
+    let text = apca_luminance(foreground);
+    let background = apca_luminance(background);
+    if (background - text).abs() < DELTA_Y_MIN {
+        return 0.0;
+    }
+    let lc = if background > text {
+        let sapc = (background.powf(NORM_BG) - text.powf(NORM_TEXT)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        let sapc = (background.powf(REV_BG) - text.powf(REV_TEXT)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+    lc * 100.0
+}
+
+fn apca_luminance(c: Srgb<u8>) -> f32 {
+    const BLACK_THRESHOLD: f32 = 0.022;
+    const BLACK_CLAMP: f32 = 1.414;
+
+    let c = c.into_format::<f32>();
+    let y =
+        0.2126729 * c.red.powf(2.4) + 0.7151522 * c.green.powf(2.4) + 0.072175 * c.blue.powf(2.4);
+    if y < BLACK_THRESHOLD {
+        y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+    } else {
+        y
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContrastThresholds {
+    pub wcag_ratio: f32,
+    pub apca_lc: f32,
+}
+
+impl Default for ContrastThresholds {
+    fn default() -> Self {
+        Self {
+            wcag_ratio: 4.5,
+            apca_lc: 60.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ContrastCheck {
+    pub foreground: ColorEntry,
+    pub background: Category,
+    pub wcag_ratio: f32,
+    pub apca_lc: f32,
+}
+
+impl ContrastCheck {
+    pub fn passes(&self, thresholds: &ContrastThresholds) -> bool {
+        self.wcag_ratio >= thresholds.wcag_ratio && self.apca_lc.abs() >= thresholds.apca_lc
+    }
+}
+
+pub fn check_contrast<T>(theme: &T) -> Vec<ContrastCheck>
+where
+    T: Theme + ?Sized,
+{
+    const TEXT_BACKGROUNDS: &[Category] = &[
+        Category::NormalBackground,
+        Category::CursorLine,
+        Category::Selection,
+        Category::DiffAdd,
+        Category::DiffChange,
+        Category::DiffDelete,
+        Category::DiffText,
+    ];
+    const CATEGORY_PAIRS: &[(Category, Category)] = &[
+        (Category::Normal, Category::NormalBackground),
+        (Category::Normal, Category::CursorLine),
+        (Category::Normal, Category::Selection),
+        (Category::Normal, Category::StatusLine),
+        (Category::LineNumber, Category::NormalBackground),
+        (Category::CursorLineNumber, Category::CursorLine),
+        (Category::ActiveTab, Category::ActiveTabBackground),
+        (Category::InactiveTab, Category::InactiveTabBackground),
+        (Category::Search, Category::SearchMatch),
+        (Category::Search, Category::ActiveSearchMatch),
+        (Category::MatchedBracket, Category::NormalBackground),
+        (Category::ModeMessage, Category::NormalBackground),
+        (Category::Question, Category::NormalBackground),
+        (Category::Special, Category::NormalBackground),
+        (Category::BadSpelling, Category::NormalBackground),
+    ];
+    let check = |foreground: ColorEntry, background: Category| {
+        let fg = foreground.color(theme);
+        let bg = theme.category_color(background);
+        ContrastCheck {
+            foreground,
+            background,
+            wcag_ratio: wcag_contrast_ratio(fg, bg),
+            apca_lc: apca_contrast(fg, bg),
+        }
+    };
+    enum_iterator::all::<Token>()
+        .flat_map(|token| {
+            TEXT_BACKGROUNDS
+                .iter()
+                .map(move |&background| (token.into(), background))
+        })
+        .chain(
+            CATEGORY_PAIRS
+                .iter()
+                .map(|&(foreground, background)| (foreground.into(), background)),
+        )
+        .chain(
+            enum_iterator::all::<DiagnosticLevel>()
+                .map(|level| (level.into(), Category::NormalBackground)),
+        )
+        .map(|(foreground, background)| check(foreground, background))
+        .collect()
+}
+
+// Moves the lightness of `color` away from `background`, keeping its hue, until the WCAG contrast
+// ratio reaches `min_ratio`.
+pub fn ensure_contrast(color: Srgb<u8>, background: Srgb<u8>, min_ratio: f32) -> Srgb<u8> {
+    if wcag_contrast_ratio(color, background) >= min_ratio {
+        return color;
+    }
+    let c = to_oklch(color);
+    let target = if to_oklch(background).l > 0.5 {
+        0.0
+    } else {
+        1.0
+    };
+    let with_l = |l| from_oklch(Oklch { l, ..c });
+    if wcag_contrast_ratio(with_l(target), background) < min_ratio {
+        return with_l(target);
+    }
+    let (mut low, mut high) = (c.l, target);
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if wcag_contrast_ratio(with_l(mid), background) >= min_ratio {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    with_l(high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    fn hex(rgb: u32) -> Srgb<u8> {
+        Srgb::from(rgb)
+    }
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn wcag_ratio_matches_reference_values() {
+        assert_close(
+            wcag_contrast_ratio(hex(0x000000), hex(0xffffff)),
+            21.0,
+            1e-3,
+        );
+        assert_close(wcag_contrast_ratio(hex(0xffffff), hex(0xffffff)), 1.0, 1e-6);
+        assert_close(
+            wcag_contrast_ratio(hex(0x777777), hex(0xffffff)),
+            4.48,
+            0.01,
+        );
+    }
+
+    #[test]
+    fn wcag_ratio_is_symmetric() {
+        let (a, b) = (hex(0x4682b4), hex(0x181818));
+        assert_eq!(wcag_contrast_ratio(a, b), wcag_contrast_ratio(b, a));
+    }
+
+    #[test]
+    fn apca_matches_reference_values() {
+        for (text, background, lc) in [
+            (0x888888, 0xffffff, 63.056),
+            (0xffffff, 0x888888, -68.541),
+            (0x000000, 0xaaaaaa, 58.146),
+            (0xaaaaaa, 0x000000, -56.24),
+            (0x112233, 0xddeeff, 91.66),
+            (0xddeeff, 0x112233, -93.07),
+        ] {
+            assert_close(apca_contrast(hex(text), hex(background)), lc, 0.1);
+        }
+    }
+
+    #[test]
+    fn apca_is_zero_for_identical_colors() {
+        assert_eq!(apca_contrast(hex(0x336699), hex(0x336699)), 0.0);
+    }
+
+    #[test]
+    fn checks_compare_against_thresholds() {
+        let checks = check_contrast(&SyntarkTheme::default());
+        let normal = checks
+            .iter()
+            .find(|c| {
+                c.foreground == Category::Normal.into()
+                    && c.background == Category::NormalBackground
+            })
+            .unwrap();
+        assert!(normal.passes(&ContrastThresholds::default()));
+        assert!(!normal.passes(&ContrastThresholds {
+            wcag_ratio: 22.0,
+            apca_lc: 0.0,
+        }));
+    }
+
+    #[test]
+    fn ensure_contrast_reaches_the_ratio() {
+        let background = hex(0x1e1e1e);
+        for color in [hex(0x333333), hex(0x4682b4), hex(0x8b0000)] {
+            let adjusted = ensure_contrast(color, background, 4.5);
+            assert!(wcag_contrast_ratio(adjusted, background) >= 4.5);
+            assert!(to_oklch(adjusted).l > to_oklch(color).l);
+        }
+        let light = hex(0xfafafa);
+        let adjusted = ensure_contrast(hex(0xffd700), light, 4.5);
+        assert!(wcag_contrast_ratio(adjusted, light) >= 4.5);
+    }
+
+    #[test]
+    fn ensure_contrast_keeps_readable_colors() {
+        let color = hex(0xeeeeee);
+        assert_eq!(ensure_contrast(color, hex(0x101010), 4.5), color);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{Category, ColorEntry, DiagnosticLevel, Theme, Token};
+use palette::{color_difference::Ciede2000, convert::FromColorUnclamped, Lab, LinSrgb, Srgb};
+use std::fmt::{self, Display};
+
+pub const DEFAULT_CVD_THRESHOLD: f32 = 10.0;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    // Machado, Oliveira and Fernandes (2009)
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    pub fn simulate(self, c: Srgb<u8>) -> Srgb<u8> {
+        let LinSrgb {
+            red, green, blue, ..
+        } = c.into_format::<f32>().into_linear();
+        let [r, g, b] = self
+            .matrix()
+            .map(|row| (row[0] * red + row[1] * green + row[2] * blue).clamp(0.0, 1.0));
+        Srgb::from_linear(LinSrgb::new(r, g, b))
+    }
+
+    #[allow(
+        clippy::inherent_to_string_shadow_display,
+        clippy::wrong_self_convention
+    )]
+    fn to_string(&self) -> String {
+        match self {
+            Self::Protanopia => "protanopia".into(),
+            Self::Deuteranopia => "deuteranopia".into(),
+            Self::Tritanopia => "tritanopia".into(),
+        }
+    }
+}
+
+impl Display for ColorVisionDeficiency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string())
+    }
+}
+
+pub fn delta_e_2000(a: Srgb<u8>, b: Srgb<u8>) -> f32 {
+    let lab = |c: Srgb<u8>| Lab::from_color_unclamped(c.into_format::<f32>());
+    lab(a).difference(lab(b))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct CvdConflict {
+    pub deficiency: ColorVisionDeficiency,
+    pub first: ColorEntry,
+    pub second: ColorEntry,
+    pub delta_e: f32,
+    pub simulated_delta_e: f32,
+}
+
+pub fn check_cvd<T>(theme: &T, threshold: f32) -> Vec<CvdConflict>
+where
+    T: Theme + ?Sized,
+{
+    const DIFF_CATEGORIES: &[Category] = &[
+        Category::DiffAdd,
+        Category::DiffChange,
+        Category::DiffDelete,
+        Category::DiffText,
+    ];
+    let groups: [Vec<ColorEntry>; 3] = [
+        enum_iterator::all::<Token>().map(Into::into).collect(),
+        enum_iterator::all::<DiagnosticLevel>()
+            .map(Into::into)
+            .collect(),
+        DIFF_CATEGORIES.iter().map(|&c| c.into()).collect(),
+    ];
+    let mut conflicts = Vec::new();
+    for entries in groups {
+        let mut colors: Vec<(ColorEntry, Srgb<u8>)> = Vec::new();
+        for entry in entries {
+            let color = entry.color(theme);
+            if colors.iter().all(|&(_, c)| c != color) {
+                colors.push((entry, color));
+            }
+        }
+        for (i, &(first, a)) in colors.iter().enumerate() {
+            for &(second, b) in &colors[i + 1..] {
+                let delta_e = delta_e_2000(a, b);
+                if delta_e < threshold {
+                    continue;
+                }
+                for deficiency in enum_iterator::all::<ColorVisionDeficiency>() {
+                    let simulated_delta_e =
+                        delta_e_2000(deficiency.simulate(a), deficiency.simulate(b));
+                    if simulated_delta_e < threshold {
+                        conflicts.push(CvdConflict {
+                            deficiency,
+                            first,
+                            second,
+                            delta_e,
+                            simulated_delta_e,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Inverted, SyntarkTheme};
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn greys_are_unchanged() {
+        for deficiency in enum_iterator::all::<ColorVisionDeficiency>() {
+            for grey in [0, 0x80, 0xff] {
+                let c = Srgb::new(grey, grey, grey);
+                let simulated = deficiency.simulate(c);
+                for (a, b) in [
+                    (simulated.red, c.red),
+                    (simulated.green, c.green),
+                    (simulated.blue, c.blue),
+                ] {
+                    assert!(a.abs_diff(b) <= 1, "{deficiency}: {simulated:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simulation_matches_the_matrices() {
+        let red = Srgb::new(0xff, 0x00, 0x00);
+        assert_eq!(
+            ColorVisionDeficiency::Protanopia.simulate(red),
+            Srgb::new(109, 95, 0)
+        );
+    }
+
+    #[test]
+    fn delta_e_matches_reference_values() {
+        let (black, white) = (Srgb::new(0, 0, 0), Srgb::new(0xff, 0xff, 0xff));
+        assert_close(delta_e_2000(black, white), 100.0, 0.01);
+        assert_eq!(delta_e_2000(white, white), 0.0);
+        let (a, b) = (Srgb::new(0x46, 0x82, 0xb4), Srgb::new(0xdc, 0x14, 0x3c));
+        assert_close(delta_e_2000(a, b), delta_e_2000(b, a), 1e-4);
+    }
+
+    #[test]
+    fn red_and_green_conflict_for_deuteranopes() {
+        let (red, green) = (Srgb::new(0xcc, 0x33, 0x33), Srgb::new(0x66, 0x99, 0x33));
+        let deuteranopia = ColorVisionDeficiency::Deuteranopia;
+        assert!(delta_e_2000(red, green) > DEFAULT_CVD_THRESHOLD);
+        assert!(
+            delta_e_2000(deuteranopia.simulate(red), deuteranopia.simulate(green))
+                < DEFAULT_CVD_THRESHOLD
+        );
+    }
+
+    #[test]
+    fn syntark_strings_and_integers_conflict_for_deuteranopes() {
+        let theme = Inverted::new(SyntarkTheme::default());
+        let pair = [
+            theme.token_color(Token::String),
+            theme.token_color(Token::Integer),
+        ];
+        assert!(check_cvd(&theme, DEFAULT_CVD_THRESHOLD)
+            .iter()
+            .any(|conflict| {
+                conflict.deficiency == ColorVisionDeficiency::Deuteranopia
+                    && pair.contains(&conflict.first.color(&theme))
+                    && pair.contains(&conflict.second.color(&theme))
+            }));
+    }
+
+    #[test]
+    fn aliased_colors_are_reported_once() {
+        let conflicts = check_cvd(&SyntarkTheme::default(), DEFAULT_CVD_THRESHOLD);
+        let alias = ColorEntry::from(Token::ConstGenericParameter);
+        assert!(conflicts
+            .iter()
+            .all(|conflict| conflict.first != alias && conflict.second != alias));
+    }
+}
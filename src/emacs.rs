@@ -0,0 +1,380 @@
+use crate::{
+    color::CssColor, output::GENERATED_MARKER, Category, DiagnosticLevel, Style, Theme, Token,
+    UnderlineStyle,
+};
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmacsTheme {
+    name: String,
+    faces: Vec<(&'static str, Style)>,
+}
+
+impl EmacsTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            faces: faces(base),
+        }
+    }
+
+    pub fn to_elisp(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let name = &self.name;
+        writeln!(
+            writer,
+            ";;; {name}-theme.el --- {GENERATED_MARKER}  -*- lexical-binding: t -*-"
+        )?;
+        writeln!(writer)?;
+        writeln!(writer, "(deftheme {name})")?;
+        writeln!(writer)?;
+        writeln!(writer, "(custom-theme-set-faces")?;
+        write!(writer, " '{name}")?;
+        for (face, style) in &self.faces {
+            write!(writer, "\n '({face} ((t {})))", face_spec(style))?;
+        }
+        writeln!(writer, ")")?;
+        writeln!(writer)?;
+        writeln!(writer, "(provide-theme '{name})")?;
+        writeln!(writer)?;
+        writeln!(writer, ";;; {name}-theme.el ends here")
+    }
+}
+
+fn faces<T: Theme + ?Sized>(base: &T) -> Vec<(&'static str, Style)> {
+    let category = |c| base.category_color(c);
+    let level = |l| base.diagnostic_level_color(l);
+    let ui = [
+        (
+            "default",
+            Style::default()
+                .foreground(category(Category::Normal))
+                .background(category(Category::NormalBackground)),
+        ),
+        (
+            "cursor",
+            Style::default().background(category(Category::TermCursor)),
+        ),
+        (
+            "fringe",
+            Style::default().background(category(Category::NormalBackground)),
+        ),
+        (
+            "region",
+            Style::default().background(category(Category::Selection)),
+        ),
+        (
+            "hl-line",
+            Style::default().background(category(Category::CursorLine)),
+        ),
+        (
+            "line-number",
+            Style::default().foreground(category(Category::LineNumber)),
+        ),
+        (
+            "line-number-current-line",
+            Style::default()
+                .foreground(category(Category::CursorLineNumber))
+                .background(category(Category::CursorLine)),
+        ),
+        (
+            "mode-line",
+            Style::default()
+                .foreground(category(Category::ActiveTab))
+                .background(category(Category::StatusLine)),
+        ),
+        (
+            "mode-line-inactive",
+            Style::default()
+                .foreground(category(Category::InactiveTab))
+                .background(category(Category::InactiveTabBackground)),
+        ),
+        (
+            "vertical-border",
+            Style::default().foreground(category(Category::MessageSeparator)),
+        ),
+        (
+            "minibuffer-prompt",
+            Style::default().foreground(category(Category::Question)),
+        ),
+        (
+            "isearch",
+            Style::default()
+                .foreground(category(Category::Search))
+                .background(category(Category::ActiveSearchMatch)),
+        ),
+        (
+            "lazy-highlight",
+            Style::default()
+                .foreground(category(Category::Search))
+                .background(category(Category::SearchMatch)),
+        ),
+        (
+            "show-paren-match",
+            Style::default()
+                .foreground(category(Category::MatchedBracket))
+                .bold(),
+        ),
+        (
+            "fill-column-indicator",
+            Style::default().foreground(category(Category::ColumnGuide)),
+        ),
+        (
+            "whitespace-space",
+            Style::default().foreground(category(Category::Whitespace)),
+        ),
+        (
+            "whitespace-tab",
+            Style::default().foreground(category(Category::Whitespace)),
+        ),
+        (
+            "whitespace-newline",
+            Style::default().foreground(category(Category::NonText)),
+        ),
+        (
+            "font-lock-builtin-face",
+            Style::default().foreground(category(Category::Special)),
+        ),
+        (
+            "font-lock-escape-face",
+            Style::default().foreground(category(Category::Special)),
+        ),
+        (
+            "tree-sitter-hl-face:escape",
+            Style::default().foreground(category(Category::Special)),
+        ),
+        (
+            "diff-added",
+            Style::default().background(category(Category::DiffAdd)),
+        ),
+        (
+            "diff-removed",
+            Style::default().background(category(Category::DiffDelete)),
+        ),
+        (
+            "diff-changed",
+            Style::default().background(category(Category::DiffChange)),
+        ),
+        (
+            "diff-refine-changed",
+            Style::default().background(category(Category::DiffText)),
+        ),
+        (
+            "flyspell-incorrect",
+            Style::default()
+                .special(category(Category::BadSpelling))
+                .curly_underline(),
+        ),
+    ];
+    let tokens = enum_iterator::all::<Token>().flat_map(|token| {
+        let style = base.token_style(token);
+        token_faces(token)
+            .iter()
+            .map(move |&face| (face, style.clone()))
+    });
+    let diagnostics = enum_iterator::all::<DiagnosticLevel>().flat_map(|l| {
+        let color = level(l);
+        let underline = Style::default().special(color).curly_underline();
+        let faces: &[_] = match l {
+            DiagnosticLevel::Error => &["flymake-error", "flycheck-error"],
+            DiagnosticLevel::Warning => &["flymake-warning", "flycheck-warning"],
+            DiagnosticLevel::Info => &["flymake-note", "flycheck-info"],
+            DiagnosticLevel::Hint => &[],
+        };
+        let foreground: &[_] = match l {
+            DiagnosticLevel::Error => &["error"],
+            DiagnosticLevel::Warning => &["warning", "font-lock-warning-face"],
+            DiagnosticLevel::Info | DiagnosticLevel::Hint => &[],
+        };
+        faces
+            .iter()
+            .map(move |&face| (face, underline.clone()))
+            .chain(
+                foreground
+                    .iter()
+                    .map(move |&face| (face, Style::default().foreground(color))),
+            )
+    });
+    ui.into_iter().chain(tokens).chain(diagnostics).collect()
+}
+
+fn token_faces(token: Token) -> &'static [&'static str] {
+    match token {
+        Token::Attribute => &["tree-sitter-hl-face:attribute"],
+        Token::Boolean => &["tree-sitter-hl-face:constant.builtin"],
+        Token::Character => &[],
+        Token::Comment => &[
+            "font-lock-comment-face",
+            "font-lock-comment-delimiter-face",
+            "tree-sitter-hl-face:comment",
+        ],
+        Token::Constant => &["font-lock-constant-face", "tree-sitter-hl-face:constant"],
+        Token::ConstGenericParameter => &[],
+        Token::Delimiter => &[
+            "font-lock-bracket-face",
+            "font-lock-delimiter-face",
+            "font-lock-punctuation-face",
+            "tree-sitter-hl-face:punctuation",
+            "tree-sitter-hl-face:punctuation.bracket",
+            "tree-sitter-hl-face:punctuation.delimiter",
+        ],
+        Token::DocComment => &["font-lock-doc-face", "tree-sitter-hl-face:doc"],
+        Token::Enum => &[],
+        Token::Field => &[
+            "font-lock-property-name-face",
+            "font-lock-property-use-face",
+            "tree-sitter-hl-face:property",
+        ],
+        Token::Float => &[],
+        Token::Function => &[
+            "font-lock-function-name-face",
+            "font-lock-function-call-face",
+            "tree-sitter-hl-face:function",
+            "tree-sitter-hl-face:function.call",
+            "tree-sitter-hl-face:method",
+            "tree-sitter-hl-face:method.call",
+        ],
+        Token::Identifier => &[],
+        Token::Integer => &["font-lock-number-face", "tree-sitter-hl-face:number"],
+        Token::Interface => &[],
+        Token::Keyword => &["font-lock-keyword-face", "tree-sitter-hl-face:keyword"],
+        Token::Link => &["link"],
+        Token::Macro => &[
+            "font-lock-preprocessor-face",
+            "tree-sitter-hl-face:function.macro",
+        ],
+        Token::Module => &[],
+        Token::Operator => &[
+            "font-lock-operator-face",
+            "font-lock-negation-char-face",
+            "tree-sitter-hl-face:operator",
+        ],
+        Token::Parameter => &["tree-sitter-hl-face:variable.parameter"],
+        Token::Static => &[],
+        Token::String => &["font-lock-string-face", "tree-sitter-hl-face:string"],
+        Token::Struct => &[],
+        Token::Tag => &["tree-sitter-hl-face:tag"],
+        Token::Todo => &["hl-todo"],
+        Token::Type => &["font-lock-type-face", "tree-sitter-hl-face:type"],
+        Token::TypeParameter => &["tree-sitter-hl-face:type.parameter"],
+        Token::Variable => &[
+            "font-lock-variable-name-face",
+            "font-lock-variable-use-face",
+            "tree-sitter-hl-face:variable",
+        ],
+        Token::Variant => &["tree-sitter-hl-face:constructor"],
+    }
+}
+
+fn face_spec(style: &Style) -> String {
+    let mut attributes = Vec::new();
+    if let Some(c) = style.foreground {
+        attributes.push(format!(r#":foreground "{}""#, CssColor(c)));
+    }
+    if let Some(c) = style.background {
+        attributes.push(format!(r#":background "{}""#, CssColor(c)));
+    }
+    if let Some(bold) = style.bold {
+        attributes.push(format!(":weight {}", if bold { "bold" } else { "normal" }));
+    }
+    if let Some(italic) = style.italic {
+        attributes.push(format!(
+            ":slant {}",
+            if italic { "italic" } else { "normal" }
+        ));
+    }
+    if let Some(strikethrough) = style.strikethrough {
+        attributes.push(format!(
+            ":strike-through {}",
+            if strikethrough { "t" } else { "nil" }
+        ));
+    }
+    if let Some(underline) = style.underline {
+        let underline_style = match underline {
+            UnderlineStyle::Single => "line",
+            UnderlineStyle::Double => "double-line",
+            UnderlineStyle::Curly => "wave",
+            UnderlineStyle::Dotted => "dots",
+            UnderlineStyle::Dashed => "dashes",
+        };
+        let color = match style.special {
+            Some(c) => format!(r#":color "{}" "#, CssColor(c)),
+            None => String::new(),
+        };
+        attributes.push(format!(":underline ({color}:style {underline_style})"));
+    }
+    if style.reversed {
+        attributes.push(":inverse-video t".into());
+    }
+    format!("({})", attributes.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    #[test]
+    fn theme_is_a_balanced_elisp_file() {
+        let output = EmacsTheme::new("syntark", &SyntarkTheme::default()).to_elisp();
+        assert_eq!(
+            output.lines().next(),
+            Some(";;; syntark-theme.el --- Generated by syntinct  -*- lexical-binding: t -*-")
+        );
+        assert!(output.contains("\n(deftheme syntark)\n"));
+        assert!(output.contains("\n(provide-theme 'syntark)\n"));
+        assert!(output.ends_with(";;; syntark-theme.el ends here\n"));
+        let mut depth = 0;
+        for c in output.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            assert!(depth >= 0);
+        }
+        assert_eq!(depth, 0);
+    }
+
+    #[test]
+    fn token_faces_carry_styles() {
+        let base = SyntarkTheme::default();
+        let output = EmacsTheme::new("syntark", &base).to_elisp();
+        let expected = format!(
+            r#" '(font-lock-keyword-face ((t (:foreground "{}" :weight bold))))"#,
+            CssColor(base.token_color(Token::Keyword))
+        );
+        assert!(output.lines().any(|line| line == expected), "{output}");
+    }
+
+    #[test]
+    fn face_specs_only_set_given_attributes() {
+        assert_eq!(face_spec(&Style::default()), "()");
+        let style = Style::default()
+            .special(palette::Srgb::new(0xff, 0, 0))
+            .curly_underline()
+            .italic();
+        assert_eq!(
+            face_spec(&style),
+            r##"(:slant italic :underline (:color "#ff0000" :style wave))"##
+        );
+        let style = Style {
+            bold: Some(false),
+            strikethrough: Some(true),
+            ..Style::reverse()
+        };
+        assert_eq!(
+            face_spec(&style),
+            "(:weight normal :strike-through t :inverse-video t)"
+        );
+    }
+}
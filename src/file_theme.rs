@@ -0,0 +1,451 @@
+use crate::{
+    color::parse_color, style::UnderlineStyle, Category, DiagnosticLevel, ParseColorError, Style,
+    Theme, Token,
+};
+use palette::Srgb;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileTheme {
+    name: Option<String>,
+    categories: HashMap<Category, Srgb<u8>>,
+    tokens: HashMap<Token, Srgb<u8>>,
+    diagnostic_levels: HashMap<DiagnosticLevel, Srgb<u8>>,
+    token_styles: HashMap<Token, Style>,
+}
+
+impl FileTheme {
+    pub fn from_path<P>(path: P) -> Result<Self, FileThemeError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let read = || {
+            fs::read_to_string(path).map_err(|source| FileThemeError::Io {
+                path: path.into(),
+                source,
+            })
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&read()?),
+            Some("json") => Self::from_json_str(&read()?),
+            _ => Err(FileThemeError::UnknownFormat(path.into())),
+        }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, FileThemeError> {
+        Self::from_document(toml::from_str(s)?)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, FileThemeError> {
+        Self::from_document(serde_json::from_str(s)?)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn from_document(doc: Document) -> Result<Self, FileThemeError> {
+        if let Some(name) = doc.name.as_ref().filter(|name| !is_valid_theme_name(name)) {
+            return Err(FileThemeError::InvalidName(name.clone()));
+        }
+        let mut resolver = Resolver {
+            doc: &doc,
+            resolved: HashMap::new(),
+            visiting: Vec::new(),
+        };
+        let categories = enum_iterator::all::<Category>()
+            .map(|c| Ok((c, resolver.resolve(ColorEntry::Category(c))?)))
+            .collect::<Result<_, FileThemeError>>()?;
+        let tokens: HashMap<_, _> = enum_iterator::all::<Token>()
+            .map(|t| Ok((t, resolver.resolve(ColorEntry::Token(t))?)))
+            .collect::<Result<_, FileThemeError>>()?;
+        let diagnostic_levels = enum_iterator::all::<DiagnosticLevel>()
+            .map(|l| Ok((l, resolver.resolve(ColorEntry::DiagnosticLevel(l))?)))
+            .collect::<Result<_, FileThemeError>>()?;
+        let token_styles = doc
+            .styles
+            .iter()
+            .map(|(&token, font)| {
+                let style = Style {
+                    bold: font.bold,
+                    italic: font.italic,
+                    strikethrough: font.strikethrough,
+                    underline: font.underline,
+                    ..Style::default().foreground(tokens[&token])
+                };
+                (token, style)
+            })
+            .collect();
+        Ok(Self {
+            name: doc.name.clone(),
+            categories,
+            tokens,
+            diagnostic_levels,
+            token_styles,
+        })
+    }
+}
+
+pub fn is_valid_theme_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl Theme for FileTheme {
+    fn category_color(&self, category: Category) -> Srgb<u8> {
+        self.categories[&category]
+    }
+
+    fn token_color(&self, token: Token) -> Srgb<u8> {
+        self.tokens[&token]
+    }
+
+    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
+        self.diagnostic_levels[&level]
+    }
+
+    fn token_style(&self, token: Token) -> Style {
+        self.token_styles
+            .get(&token)
+            .cloned()
+            .unwrap_or_else(|| Style::default().foreground(self.token_color(token)))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileThemeError {
+    #[error("Failed to read theme file {path}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Unknown theme file format for {0}; expected a `.toml` or `.json` extension")]
+    UnknownFormat(PathBuf),
+    #[error("Invalid theme name `{0}`; expected only ASCII letters, digits, `-` and `_`")]
+    InvalidName(String),
+    #[error("Failed to parse TOML theme")]
+    Toml(#[from] toml::de::Error),
+    #[error("Failed to parse JSON theme")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid color for {entry}")]
+    InvalidColor {
+        entry: ColorEntry,
+        #[source]
+        source: ParseColorError,
+    },
+    #[error("Missing color for {0}")]
+    MissingColor(ColorEntry),
+    #[error("Cyclic color reference involving {0}")]
+    CyclicReference(ColorEntry),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorEntry {
+    Category(Category),
+    Token(Token),
+    DiagnosticLevel(DiagnosticLevel),
+}
+
+impl ColorEntry {
+    pub fn color<T>(self, theme: &T) -> Srgb<u8>
+    where
+        T: Theme + ?Sized,
+    {
+        match self {
+            Self::Category(c) => theme.category_color(c),
+            Self::Token(t) => theme.token_color(t),
+            Self::DiagnosticLevel(l) => theme.diagnostic_level_color(l),
+        }
+    }
+}
+
+impl From<Category> for ColorEntry {
+    fn from(category: Category) -> Self {
+        Self::Category(category)
+    }
+}
+
+impl From<Token> for ColorEntry {
+    fn from(token: Token) -> Self {
+        Self::Token(token)
+    }
+}
+
+impl From<DiagnosticLevel> for ColorEntry {
+    fn from(level: DiagnosticLevel) -> Self {
+        Self::DiagnosticLevel(level)
+    }
+}
+
+impl Display for ColorEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Category(c) => write!(f, "category {c:?}"),
+            Self::Token(t) => write!(f, "token {t:?}"),
+            Self::DiagnosticLevel(l) => write!(f, "diagnostic level {l:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Document {
+    name: Option<String>,
+    #[serde(default)]
+    categories: HashMap<Category, ColorSpec>,
+    #[serde(default)]
+    tokens: HashMap<Token, ColorSpec>,
+    #[serde(default)]
+    diagnostic_levels: HashMap<DiagnosticLevel, ColorSpec>,
+    #[serde(default)]
+    styles: HashMap<Token, FontStyle>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FontStyle {
+    bold: Option<bool>,
+    italic: Option<bool>,
+    strikethrough: Option<bool>,
+    underline: Option<UnderlineStyle>,
+}
+
+impl Document {
+    fn get(&self, entry: ColorEntry) -> Option<&ColorSpec> {
+        match entry {
+            ColorEntry::Category(c) => self.categories.get(&c),
+            ColorEntry::Token(t) => self.tokens.get(&t),
+            ColorEntry::DiagnosticLevel(l) => self.diagnostic_levels.get(&l),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ColorSpec {
+    Value(String),
+    Reference(ColorEntry),
+}
+
+#[derive(Debug)]
+struct Resolver<'a> {
+    doc: &'a Document,
+    resolved: HashMap<ColorEntry, Srgb<u8>>,
+    visiting: Vec<ColorEntry>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, entry: ColorEntry) -> Result<Srgb<u8>, FileThemeError> {
+        if let Some(&color) = self.resolved.get(&entry) {
+            return Ok(color);
+        }
+        if self.visiting.contains(&entry) {
+            return Err(FileThemeError::CyclicReference(entry));
+        }
+        let color = match self.doc.get(entry) {
+            Some(ColorSpec::Value(s)) => {
+                parse_color(s).map_err(|source| FileThemeError::InvalidColor { entry, source })?
+            }
+            Some(&ColorSpec::Reference(target)) => {
+                self.visiting.push(entry);
+                let color = self.resolve(target);
+                self.visiting.pop();
+                color?
+            }
+            None => return Err(FileThemeError::MissingColor(entry)),
+        };
+        self.resolved.insert(entry, color);
+        Ok(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key<T: serde::Serialize>(value: T) -> String {
+        serde_json::to_value(value)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .into()
+    }
+
+    fn complete_document() -> toml::Table {
+        fn section<T: serde::Serialize>(values: impl Iterator<Item = T>) -> toml::Value {
+            toml::Value::Table(
+                values
+                    .map(|v| (key(v), toml::Value::String("#102030".into())))
+                    .collect(),
+            )
+        }
+        let mut doc = toml::Table::new();
+        doc.insert(
+            "categories".into(),
+            section(enum_iterator::all::<Category>()),
+        );
+        doc.insert("tokens".into(), section(enum_iterator::all::<Token>()));
+        doc.insert(
+            "diagnostic_levels".into(),
+            section(enum_iterator::all::<DiagnosticLevel>()),
+        );
+        doc
+    }
+
+    fn set(doc: &mut toml::Table, section: &str, key: &str, value: toml::Value) {
+        doc[section]
+            .as_table_mut()
+            .unwrap()
+            .insert(key.into(), value);
+    }
+
+    fn load(doc: &toml::Table) -> Result<FileTheme, FileThemeError> {
+        FileTheme::from_toml_str(&doc.to_string())
+    }
+
+    #[test]
+    fn complete_document_loads() {
+        let mut doc = complete_document();
+        set(&mut doc, "tokens", "keyword", "Crimson".into());
+        let theme = load(&doc).unwrap();
+        assert_eq!(theme.name(), None);
+        assert_eq!(
+            theme.category_color(Category::Normal),
+            Srgb::new(0x10, 0x20, 0x30)
+        );
+        assert_eq!(
+            theme.token_color(Token::Keyword),
+            Srgb::new(0xdc, 0x14, 0x3c)
+        );
+    }
+
+    #[test]
+    fn references_are_resolved_transitively() {
+        let mut doc = complete_document();
+        set(&mut doc, "tokens", "integer", "#abcdef".into());
+        let reference = |kind: &str, name: &str| {
+            toml::Value::Table([(kind.into(), name.into())].into_iter().collect())
+        };
+        set(&mut doc, "tokens", "float", reference("token", "integer"));
+        set(
+            &mut doc,
+            "categories",
+            "search",
+            reference("token", "float"),
+        );
+        let theme = load(&doc).unwrap();
+        assert_eq!(theme.token_color(Token::Float), Srgb::new(0xab, 0xcd, 0xef));
+        assert_eq!(
+            theme.category_color(Category::Search),
+            Srgb::new(0xab, 0xcd, 0xef)
+        );
+    }
+
+    #[test]
+    fn cyclic_references_are_rejected() {
+        let mut doc = complete_document();
+        let reference =
+            |name: &str| toml::Value::Table([("token".into(), name.into())].into_iter().collect());
+        set(&mut doc, "tokens", "integer", reference("float"));
+        set(&mut doc, "tokens", "float", reference("integer"));
+        assert!(matches!(
+            load(&doc),
+            Err(FileThemeError::CyclicReference(_))
+        ));
+    }
+
+    #[test]
+    fn missing_color_is_reported() {
+        let mut doc = complete_document();
+        doc["tokens"].as_table_mut().unwrap().remove("keyword");
+        assert!(matches!(
+            load(&doc),
+            Err(FileThemeError::MissingColor(ColorEntry::Token(
+                Token::Keyword
+            )))
+        ));
+    }
+
+    #[test]
+    fn invalid_color_is_reported() {
+        let mut doc = complete_document();
+        set(&mut doc, "categories", "normal", "#12345".into());
+        assert!(matches!(
+            load(&doc),
+            Err(FileThemeError::InvalidColor {
+                entry: ColorEntry::Category(Category::Normal),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let mut doc = complete_document();
+        doc.insert("colours".into(), toml::Value::Table(toml::Table::new()));
+        assert!(matches!(load(&doc), Err(FileThemeError::Toml(_))));
+    }
+
+    #[test]
+    fn styles_keep_the_token_color() {
+        let mut doc = complete_document();
+        let style = [("bold".to_string(), toml::Value::Boolean(true))]
+            .into_iter()
+            .collect();
+        doc.insert(
+            "styles".into(),
+            toml::Value::Table(
+                [("keyword".to_string(), toml::Value::Table(style))]
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+        let theme = load(&doc).unwrap();
+        let style = theme.token_style(Token::Keyword);
+        assert_eq!(style.bold, Some(true));
+        assert_eq!(style.foreground, Some(Srgb::new(0x10, 0x20, 0x30)));
+        assert_eq!(theme.token_style(Token::Comment).bold, None);
+    }
+
+    #[test]
+    fn json_documents_load() {
+        let doc = serde_json::to_string(&complete_document()).unwrap();
+        assert!(FileTheme::from_json_str(&doc).is_ok());
+    }
+
+    #[test]
+    fn valid_names_are_kept() {
+        let mut doc = complete_document();
+        doc.insert("name".into(), "my-theme_2".into());
+        assert_eq!(load(&doc).unwrap().name(), Some("my-theme_2"));
+    }
+
+    #[test]
+    fn names_that_could_escape_the_output_are_rejected() {
+        for name in [
+            r#"x"; os.execute("touch pwned") --"#,
+            "../../init",
+            "two words",
+            "line\nbreak",
+            "",
+        ] {
+            let mut doc = complete_document();
+            doc.insert("name".into(), name.into());
+            assert!(
+                matches!(load(&doc), Err(FileThemeError::InvalidName(_))),
+                "{name:?}"
+            );
+        }
+    }
+}
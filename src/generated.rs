@@ -0,0 +1,322 @@
+use crate::{
+    adjust_lightness, delta_e_2000, ensure_contrast, from_oklch, mix, to_oklch,
+    wcag_contrast_ratio, Category, DiagnosticLevel, Theme, Token,
+};
+use palette::{Oklch, Srgb};
+use std::collections::HashMap;
+
+const MIN_CONTRAST: f32 = 4.5;
+const MIN_DELTA_E: f32 = 12.0;
+const CHROMA: f32 = 0.12;
+const LIGHTNESS_OFFSETS: [f32; 17] = [
+    0.0, 0.04, -0.04, 0.08, -0.08, 0.12, -0.12, 0.16, -0.16, 0.2, -0.2, 0.24, -0.24, 0.28, -0.28,
+    0.32, -0.32,
+];
+const HUE_OFFSETS: [f32; 7] = [0.0, 10.0, -10.0, 20.0, -20.0, 30.0, -30.0];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+enum Role {
+    Keyword,
+    Function,
+    String,
+    Type,
+    Integer,
+    Variant,
+    Constant,
+    Attribute,
+    Module,
+    Interface,
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratedTheme {
+    background: Srgb<u8>,
+    foreground: Srgb<u8>,
+    roles: HashMap<Role, Srgb<u8>>,
+    diagnostic_levels: HashMap<DiagnosticLevel, Srgb<u8>>,
+}
+
+impl GeneratedTheme {
+    pub fn new(
+        background: Srgb<u8>,
+        foreground: Srgb<u8>,
+        accents: &[Srgb<u8>],
+    ) -> Result<Self, GeneratedThemeError> {
+        let dark = to_oklch(background).l <= 0.5;
+        let lightness = if dark { 0.78 } else { 0.45 };
+        let readable = |c: Srgb<u8>| {
+            let c = ensure_contrast(c, background, MIN_CONTRAST);
+            (wcag_contrast_ratio(c, background) >= MIN_CONTRAST).then_some(c)
+        };
+        let accent_hues = accents
+            .iter()
+            .map(|&c| to_oklch(c).hue.into_positive_degrees())
+            .collect::<Vec<_>>();
+        let hues = spread_hues(&accent_hues, enum_iterator::cardinality::<Role>());
+        let mut chosen = Vec::<Srgb<u8>>::new();
+        let mut distinct = |chroma: f32, hue: f32| {
+            let candidates = HUE_OFFSETS.iter().flat_map(|&hue_offset| {
+                LIGHTNESS_OFFSETS.iter().map(move |&lightness_offset| {
+                    Oklch::new(lightness + lightness_offset, chroma, hue + hue_offset)
+                })
+            });
+            let color = candidates
+                .filter_map(|c| readable(from_oklch(c)))
+                .find(|&c| chosen.iter().all(|&r| delta_e_2000(r, c) >= MIN_DELTA_E))
+                .ok_or(GeneratedThemeError::IndistinctColors)?;
+            chosen.push(color);
+            Ok(color)
+        };
+        let roles = enum_iterator::all::<Role>()
+            .zip(hues)
+            .map(|(role, hue)| Ok((role, distinct(CHROMA, hue)?)))
+            .collect::<Result<HashMap<_, _>, GeneratedThemeError>>()?;
+        let diagnostic_levels = enum_iterator::all::<DiagnosticLevel>()
+            .map(|level| {
+                let hue = match level {
+                    DiagnosticLevel::Error => 25.0,
+                    DiagnosticLevel::Warning => 80.0,
+                    DiagnosticLevel::Info => 250.0,
+                    DiagnosticLevel::Hint => 190.0,
+                };
+                Ok((level, distinct(CHROMA + 0.04, hue)?))
+            })
+            .collect::<Result<_, GeneratedThemeError>>()?;
+        Ok(Self {
+            background,
+            foreground: readable(foreground).ok_or(GeneratedThemeError::LowContrast)?,
+            roles,
+            diagnostic_levels,
+        })
+    }
+
+    fn role(&self, role: Role) -> Srgb<u8> {
+        self.roles[&role]
+    }
+
+    fn surface(&self, delta: f32) -> Srgb<u8> {
+        adjust_lightness(self.background, self.towards_foreground(delta))
+    }
+
+    fn tinted_surface(&self, delta: f32, chroma: f32, hue: f32) -> Srgb<u8> {
+        let l = to_oklch(self.background).l + self.towards_foreground(delta);
+        from_oklch(Oklch::new(l, chroma, hue))
+    }
+
+    fn towards_foreground(&self, delta: f32) -> f32 {
+        if to_oklch(self.background).l <= 0.5 {
+            delta
+        } else {
+            -delta
+        }
+    }
+
+    fn dimmed(&self) -> Srgb<u8> {
+        ensure_contrast(
+            mix(self.foreground, self.background, 0.45),
+            self.background,
+            MIN_CONTRAST,
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GeneratedThemeError {
+    #[error("No color reaches a contrast ratio of {MIN_CONTRAST} with the seed background")]
+    LowContrast,
+    #[error("Failed to find distinct token colors for the seed colors")]
+    IndistinctColors,
+}
+
+impl Theme for GeneratedTheme {
+    fn category_color(&self, category: Category) -> Srgb<u8> {
+        match category {
+            Category::ActiveSearchMatch => ensure_contrast(
+                self.tinted_surface(0.3, 0.12, to_oklch(self.role(Role::Keyword)).hue.into()),
+                self.foreground,
+                MIN_CONTRAST,
+            ),
+            Category::ActiveTab => self.foreground,
+            Category::ActiveTabBackground => self.background,
+            Category::BadSpelling => self.diagnostic_level_color(DiagnosticLevel::Error),
+            Category::ColumnGuide => self.surface(0.03),
+            Category::CursorLine => self.surface(0.05),
+            Category::CursorLineNumber => self.foreground,
+            Category::DiffAdd => self.tinted_surface(0.08, 0.05, 145.0),
+            Category::DiffChange => self.tinted_surface(0.08, 0.05, 250.0),
+            Category::DiffDelete => self.tinted_surface(0.08, 0.05, 25.0),
+            Category::DiffText => self.tinted_surface(0.14, 0.07, 250.0),
+            Category::Folded => self.surface(0.03),
+            Category::InactiveTab => self.dimmed(),
+            Category::InactiveTabBackground => self.surface(0.03),
+            Category::LineNumber => self.dimmed(),
+            Category::MatchedBracket => self.role(Role::Function),
+            Category::MessageSeparator => self.surface(0.1),
+            Category::ModeMessage => self.foreground,
+            Category::NonText => mix(self.foreground, self.background, 0.7),
+            Category::Normal => self.foreground,
+            Category::NormalBackground => self.background,
+            Category::Question => self.role(Role::Function),
+            Category::Search => self.foreground,
+            Category::SearchMatch => ensure_contrast(
+                self.tinted_surface(0.2, 0.08, to_oklch(self.role(Role::Keyword)).hue.into()),
+                self.foreground,
+                MIN_CONTRAST,
+            ),
+            Category::Selection => self.surface(0.1),
+            Category::Special => self.role(Role::Module),
+            Category::StatusLine => self.surface(0.03),
+            Category::TermCursor => self.foreground,
+            Category::UnfocusedTermCursor => self.dimmed(),
+            Category::Whitespace => mix(self.foreground, self.background, 0.7),
+        }
+    }
+
+    fn token_color(&self, token: Token) -> Srgb<u8> {
+        match token {
+            Token::Attribute => self.role(Role::Attribute),
+            Token::Boolean => self.role(Role::Variant),
+            Token::Character => self.role(Role::String),
+            Token::Comment => self.dimmed(),
+            Token::Constant => self.role(Role::Constant),
+            Token::ConstGenericParameter => self.role(Role::Constant),
+            Token::Delimiter => self.foreground,
+            Token::DocComment => self.dimmed(),
+            Token::Enum => self.role(Role::Type),
+            Token::Field => self.foreground,
+            Token::Float => self.role(Role::Integer),
+            Token::Function => self.role(Role::Function),
+            Token::Identifier => self.foreground,
+            Token::Integer => self.role(Role::Integer),
+            Token::Interface => self.role(Role::Interface),
+            Token::Keyword => self.role(Role::Keyword),
+            Token::Link => self.role(Role::Module),
+            Token::Macro => self.role(Role::Attribute),
+            Token::Module => self.role(Role::Module),
+            Token::Operator => self.role(Role::Keyword),
+            Token::Parameter => self.foreground,
+            Token::Static => self.role(Role::Constant),
+            Token::String => self.role(Role::String),
+            Token::Struct => self.role(Role::Type),
+            Token::Tag => self.role(Role::Keyword),
+            Token::Todo => self.diagnostic_level_color(DiagnosticLevel::Warning),
+            Token::Type => self.role(Role::Type),
+            Token::TypeParameter => self.role(Role::Interface),
+            Token::Variable => self.foreground,
+            Token::Variant => self.role(Role::Variant),
+        }
+    }
+
+    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
+        self.diagnostic_levels[&level]
+    }
+}
+
+fn spread_hues(accents: &[f32], count: usize) -> Vec<f32> {
+    let mut hues = Vec::<f32>::new();
+    for &hue in accents {
+        if hues.iter().all(|&h| (h - hue).abs() > 0.5) {
+            hues.push(hue);
+        }
+    }
+    if hues.is_empty() {
+        hues.push(0.0);
+    }
+    let mut sorted = hues.clone();
+    sorted.sort_by(f32::total_cmp);
+    let n = sorted.len();
+    let gaps = (0..n)
+        .map(|i| {
+            if n == 1 {
+                360.0
+            } else {
+                (sorted[(i + 1) % n] - sorted[i]).rem_euclid(360.0)
+            }
+        })
+        .collect::<Vec<_>>();
+    let extra = count.saturating_sub(n);
+    let shares = gaps
+        .iter()
+        .map(|gap| extra as f32 * gap / 360.0)
+        .collect::<Vec<_>>();
+    let mut counts = shares.iter().map(|&s| s as usize).collect::<Vec<_>>();
+    let mut by_remainder = (0..n).collect::<Vec<_>>();
+    by_remainder.sort_by(|&a, &b| shares[b].fract().total_cmp(&shares[a].fract()));
+    let missing = extra - counts.iter().sum::<usize>();
+    for &i in by_remainder.iter().take(missing) {
+        counts[i] += 1;
+    }
+    for i in 0..n {
+        let step = gaps[i] / (counts[i] + 1) as f32;
+        hues.extend((1..=counts[i]).map(|k| (sorted[i] + step * k as f32).rem_euclid(360.0)));
+    }
+    hues.truncate(count);
+    hues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_hues(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-3, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn hues_are_spread_around_a_single_accent() {
+        assert_hues(&spread_hues(&[30.0], 4), &[30.0, 120.0, 210.0, 300.0]);
+        assert_hues(&spread_hues(&[], 2), &[0.0, 180.0]);
+    }
+
+    #[test]
+    fn hues_fill_gaps_in_proportion() {
+        assert_hues(
+            &spread_hues(&[0.0, 90.0], 6),
+            &[0.0, 90.0, 45.0, 157.5, 225.0, 292.5],
+        );
+    }
+
+    #[test]
+    fn duplicate_accents_are_merged() {
+        assert_hues(&spread_hues(&[100.0, 100.2, 280.0], 2), &[100.0, 280.0]);
+    }
+
+    #[test]
+    fn roles_are_readable_and_distinct() {
+        let seeds = [
+            (0x1a1b26, 0xc0caf5, vec![0xbb9af7, 0x7aa2f7]),
+            (0xfafafa, 0x383a42, vec![0xa626a4]),
+            (0x000000, 0xffffff, vec![0x00ff00]),
+        ];
+        for (background, foreground, accents) in seeds {
+            let background = Srgb::from(background);
+            let accents = accents.into_iter().map(Srgb::from).collect::<Vec<_>>();
+            let theme = GeneratedTheme::new(background, foreground.into(), &accents).unwrap();
+            let colors = theme
+                .roles
+                .values()
+                .chain(theme.diagnostic_levels.values())
+                .copied()
+                .collect::<Vec<_>>();
+            for (i, &a) in colors.iter().enumerate() {
+                assert!(wcag_contrast_ratio(a, background) >= MIN_CONTRAST);
+                for &b in &colors[i + 1..] {
+                    assert!(delta_e_2000(a, b) >= MIN_DELTA_E, "{a:?} {b:?}");
+                }
+            }
+            assert!(wcag_contrast_ratio(theme.foreground, background) >= MIN_CONTRAST);
+        }
+    }
+
+    #[test]
+    fn mid_grey_backgrounds_are_rejected() {
+        let grey = Srgb::new(0x77, 0x77, 0x77);
+        assert!(matches!(
+            GeneratedTheme::new(grey, Srgb::new(0, 0, 0), &[Srgb::new(0xff, 0, 0)]),
+            Err(GeneratedThemeError::IndistinctColors)
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    output::GENERATED_MARKER, style::UnderlineStyle, Category, ColorEntry, DiagnosticLevel, Theme,
+    Token,
+};
+use heck::ToSnakeCase;
+use palette::Srgb;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HelixTheme {
+    name: String,
+    palette: BTreeMap<String, Srgb<u8>>,
+    scopes: Vec<(&'static str, ScopeStyle)>,
+}
+
+impl HelixTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            palette: enum_iterator::all::<ColorEntry>()
+                .map(|entry| (palette_key(entry), entry.color(base)))
+                .collect(),
+            scopes: build_scopes()
+                .into_iter()
+                .map(|(scope, style)| (scope, style.with_token_style(base)))
+                .collect(),
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "# {GENERATED_MARKER}")?;
+        writeln!(writer, "# {}", self.name)?;
+        writeln!(writer)?;
+        for (scope, style) in &self.scopes {
+            writeln!(writer, r#""{scope}" = {style}"#)?;
+        }
+        writeln!(writer)?;
+        writeln!(writer, "[palette]")?;
+        for (key, &color) in &self.palette {
+            let Srgb {
+                red, green, blue, ..
+            } = color;
+            writeln!(writer, r##"{key} = "#{red:02x}{green:02x}{blue:02x}""##)?;
+        }
+        Ok(())
+    }
+}
+
+fn palette_key(entry: ColorEntry) -> String {
+    match entry {
+        ColorEntry::Category(c) => format!("{c:?}"),
+        ColorEntry::Token(t) => format!("{t:?}"),
+        ColorEntry::DiagnosticLevel(l) => format!("{l:?}"),
+    }
+    .to_snake_case()
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Modifier {
+    Bold,
+    Dim,
+    Italic,
+    CrossedOut,
+    Reversed,
+}
+
+impl Modifier {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bold => "bold",
+            Self::Dim => "dim",
+            Self::Italic => "italic",
+            Self::CrossedOut => "crossed_out",
+            Self::Reversed => "reversed",
+        }
+    }
+}
+
+fn underline_style_as_str(style: UnderlineStyle) -> &'static str {
+    match style {
+        UnderlineStyle::Single => "line",
+        UnderlineStyle::Double => "double_line",
+        UnderlineStyle::Curly => "curl",
+        UnderlineStyle::Dotted => "dotted",
+        UnderlineStyle::Dashed => "dashed",
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ScopeStyle {
+    fg: Option<ColorEntry>,
+    bg: Option<ColorEntry>,
+    underline: Option<(ColorEntry, UnderlineStyle)>,
+    modifiers: Vec<Modifier>,
+}
+
+impl ScopeStyle {
+    fn fg<C: Into<ColorEntry>>(color: C) -> Self {
+        Self::default().with_fg(color)
+    }
+
+    fn bg<C: Into<ColorEntry>>(color: C) -> Self {
+        Self::default().with_bg(color)
+    }
+
+    fn modifier(modifier: Modifier) -> Self {
+        Self::default().with_modifier(modifier)
+    }
+
+    fn with_fg<C: Into<ColorEntry>>(self, color: C) -> Self {
+        Self {
+            fg: Some(color.into()),
+            ..self
+        }
+    }
+
+    fn with_bg<C: Into<ColorEntry>>(self, color: C) -> Self {
+        Self {
+            bg: Some(color.into()),
+            ..self
+        }
+    }
+
+    fn with_underline<C: Into<ColorEntry>>(self, color: C, style: UnderlineStyle) -> Self {
+        Self {
+            underline: Some((color.into(), style)),
+            ..self
+        }
+    }
+
+    fn with_modifier(mut self, modifier: Modifier) -> Self {
+        if !self.modifiers.contains(&modifier) {
+            self.modifiers.push(modifier);
+        }
+        self
+    }
+
+    fn with_token_style<T>(self, base: &T) -> Self
+    where
+        T: Theme + ?Sized,
+    {
+        let Some(ColorEntry::Token(token)) = self.fg else {
+            return self;
+        };
+        let style = base.token_style(token);
+        let flags = [
+            (style.bold, Modifier::Bold),
+            (style.italic, Modifier::Italic),
+            (style.strikethrough, Modifier::CrossedOut),
+            (Some(style.reversed), Modifier::Reversed),
+        ];
+        let mut scope_style = flags
+            .into_iter()
+            .filter(|&(flag, _)| flag == Some(true))
+            .fold(self, |acc, (_, modifier)| acc.with_modifier(modifier));
+        if let (None, Some(underline)) = (scope_style.underline, style.underline) {
+            scope_style = scope_style.with_underline(token, underline);
+        }
+        scope_style
+    }
+}
+
+impl Display for ScopeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut fields = Vec::new();
+        if let Some(fg) = self.fg {
+            fields.push(format!(r#"fg = "{}""#, palette_key(fg)));
+        }
+        if let Some(bg) = self.bg {
+            fields.push(format!(r#"bg = "{}""#, palette_key(bg)));
+        }
+        if let Some((color, style)) = self.underline {
+            fields.push(format!(
+                r#"underline = {{ color = "{}", style = "{}" }}"#,
+                palette_key(color),
+                underline_style_as_str(style),
+            ));
+        }
+        if !self.modifiers.is_empty() {
+            let modifiers = self
+                .modifiers
+                .iter()
+                .map(|m| format!(r#""{}""#, m.as_str()))
+                .collect::<Vec<_>>();
+            fields.push(format!("modifiers = [{}]", modifiers.join(", ")));
+        }
+        write!(f, "{{ {} }}", fields.join(", "))
+    }
+}
+
+fn build_scopes() -> Vec<(&'static str, ScopeStyle)> {
+    vec![
+        ("attribute", ScopeStyle::fg(Token::Attribute)),
+        ("type", ScopeStyle::fg(Token::Type)),
+        ("type.builtin", ScopeStyle::fg(Token::Type)),
+        ("type.parameter", ScopeStyle::fg(Token::TypeParameter)),
+        ("type.enum", ScopeStyle::fg(Token::Enum)),
+        ("type.enum.variant", ScopeStyle::fg(Token::Variant)),
+        ("constructor", ScopeStyle::fg(Token::Variant)),
+        ("constant", ScopeStyle::fg(Token::Constant)),
+        ("constant.builtin.boolean", ScopeStyle::fg(Token::Boolean)),
+        ("constant.character", ScopeStyle::fg(Token::Character)),
+        (
+            "constant.character.escape",
+            ScopeStyle::fg(Category::Special),
+        ),
+        ("constant.numeric", ScopeStyle::fg(Token::Integer)),
+        ("constant.numeric.integer", ScopeStyle::fg(Token::Integer)),
+        ("constant.numeric.float", ScopeStyle::fg(Token::Float)),
+        ("string", ScopeStyle::fg(Token::String)),
+        ("string.regexp", ScopeStyle::fg(Category::Special)),
+        ("string.special", ScopeStyle::fg(Category::Special)),
+        ("string.special.url", ScopeStyle::fg(Token::Link)),
+        ("comment", ScopeStyle::fg(Token::Comment)),
+        (
+            "comment.block.documentation",
+            ScopeStyle::fg(Token::DocComment),
+        ),
+        (
+            "comment.line.documentation",
+            ScopeStyle::fg(Token::DocComment),
+        ),
+        ("variable", ScopeStyle::fg(Token::Variable)),
+        ("variable.builtin", ScopeStyle::fg(Token::Keyword)),
+        ("variable.parameter", ScopeStyle::fg(Token::Parameter)),
+        ("variable.other.member", ScopeStyle::fg(Token::Field)),
+        ("label", ScopeStyle::fg(Token::Keyword)),
+        ("punctuation", ScopeStyle::fg(Token::Delimiter)),
+        ("keyword", ScopeStyle::fg(Token::Keyword)),
+        ("keyword.operator", ScopeStyle::fg(Token::Operator)),
+        ("keyword.directive", ScopeStyle::fg(Token::Macro)),
+        ("operator", ScopeStyle::fg(Token::Operator)),
+        ("function", ScopeStyle::fg(Token::Function)),
+        ("function.macro", ScopeStyle::fg(Token::Macro)),
+        ("tag", ScopeStyle::fg(Token::Tag)),
+        ("namespace", ScopeStyle::fg(Token::Module)),
+        ("special", ScopeStyle::fg(Category::Special)),
+        ("markup.heading", ScopeStyle::fg(Token::Module)),
+        ("markup.heading.marker", ScopeStyle::fg(Token::Delimiter)),
+        ("markup.bold", ScopeStyle::modifier(Modifier::Bold)),
+        ("markup.italic", ScopeStyle::modifier(Modifier::Italic)),
+        (
+            "markup.strikethrough",
+            ScopeStyle::modifier(Modifier::CrossedOut),
+        ),
+        ("markup.link.url", ScopeStyle::fg(Token::Link)),
+        ("markup.link.text", ScopeStyle::fg(Token::Link)),
+        ("markup.raw", ScopeStyle::fg(Token::Identifier)),
+        ("markup.raw.block", ScopeStyle::fg(Token::String)),
+        ("diff.plus", ScopeStyle::fg(Token::String)),
+        ("diff.minus", ScopeStyle::fg(DiagnosticLevel::Error)),
+        ("diff.delta", ScopeStyle::fg(DiagnosticLevel::Warning)),
+        ("ui.background", ScopeStyle::bg(Category::NormalBackground)),
+        ("ui.text", ScopeStyle::fg(Category::Normal)),
+        ("ui.text.focus", ScopeStyle::bg(Category::Selection)),
+        ("ui.cursor", ScopeStyle::modifier(Modifier::Reversed)),
+        ("ui.cursor.match", ScopeStyle::fg(Category::MatchedBracket)),
+        ("ui.cursorline", ScopeStyle::bg(Category::CursorLine)),
+        (
+            "ui.cursorline.primary",
+            ScopeStyle::bg(Category::CursorLine),
+        ),
+        ("ui.selection", ScopeStyle::bg(Category::Selection)),
+        ("ui.linenr", ScopeStyle::fg(Category::LineNumber)),
+        (
+            "ui.linenr.selected",
+            ScopeStyle::fg(Category::CursorLineNumber),
+        ),
+        (
+            "ui.statusline",
+            ScopeStyle::fg(Category::Normal).with_bg(Category::StatusLine),
+        ),
+        (
+            "ui.statusline.inactive",
+            ScopeStyle::fg(Category::LineNumber).with_bg(Category::StatusLine),
+        ),
+        (
+            "ui.bufferline",
+            ScopeStyle::fg(Category::InactiveTab).with_bg(Category::InactiveTabBackground),
+        ),
+        (
+            "ui.bufferline.active",
+            ScopeStyle::fg(Category::ActiveTab).with_bg(Category::ActiveTabBackground),
+        ),
+        (
+            "ui.bufferline.background",
+            ScopeStyle::bg(Category::InactiveTabBackground),
+        ),
+        ("ui.popup", ScopeStyle::bg(Category::StatusLine)),
+        ("ui.menu", ScopeStyle::bg(Category::StatusLine)),
+        ("ui.menu.selected", ScopeStyle::bg(Category::Selection)),
+        ("ui.window", ScopeStyle::fg(Category::NonText)),
+        ("ui.help", ScopeStyle::bg(Category::StatusLine)),
+        (
+            "ui.virtual.whitespace",
+            ScopeStyle::fg(Category::Whitespace),
+        ),
+        (
+            "ui.virtual.indent-guide",
+            ScopeStyle::fg(Category::Whitespace),
+        ),
+        ("ui.virtual.ruler", ScopeStyle::bg(Category::ColumnGuide)),
+        ("ui.virtual.inlay-hint", ScopeStyle::fg(Category::NonText)),
+        ("ui.highlight", ScopeStyle::bg(Category::SearchMatch)),
+        (
+            "ui.text.inactive",
+            ScopeStyle::fg(Category::NonText).with_modifier(Modifier::Dim),
+        ),
+        ("error", ScopeStyle::fg(DiagnosticLevel::Error)),
+        ("warning", ScopeStyle::fg(DiagnosticLevel::Warning)),
+        ("info", ScopeStyle::fg(DiagnosticLevel::Info)),
+        ("hint", ScopeStyle::fg(DiagnosticLevel::Hint)),
+        (
+            "diagnostic.error",
+            ScopeStyle::default().with_underline(DiagnosticLevel::Error, UnderlineStyle::Curly),
+        ),
+        (
+            "diagnostic.warning",
+            ScopeStyle::default().with_underline(DiagnosticLevel::Warning, UnderlineStyle::Curly),
+        ),
+        (
+            "diagnostic.info",
+            ScopeStyle::default().with_underline(DiagnosticLevel::Info, UnderlineStyle::Curly),
+        ),
+        (
+            "diagnostic.hint",
+            ScopeStyle::default().with_underline(DiagnosticLevel::Hint, UnderlineStyle::Curly),
+        ),
+        (
+            "diagnostic.deprecated",
+            ScopeStyle::modifier(Modifier::CrossedOut),
+        ),
+        (
+            "diagnostic.unnecessary",
+            ScopeStyle::modifier(Modifier::Dim),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::CssColor, SyntarkTheme};
+
+    fn syntark() -> (SyntarkTheme, String) {
+        let base = SyntarkTheme::default();
+        let toml = HelixTheme::new("syntark", &base).to_toml();
+        (base, toml)
+    }
+
+    #[test]
+    fn first_line_is_the_generated_marker() {
+        let (_, toml) = syntark();
+        assert_eq!(
+            toml.lines().next(),
+            Some(format!("# {GENERATED_MARKER}").as_str())
+        );
+    }
+
+    #[test]
+    fn scopes_refer_to_palette_colors() {
+        let (base, toml) = syntark();
+        let table = toml.parse::<toml::Table>().unwrap();
+        let palette = table["palette"].as_table().unwrap();
+        assert_eq!(table["keyword"]["fg"].as_str(), Some("keyword"));
+        assert_eq!(
+            palette["keyword"].as_str(),
+            Some(
+                CssColor(base.token_color(Token::Keyword))
+                    .to_string()
+                    .as_str()
+            )
+        );
+        for (scope, style) in table.iter().filter(|(scope, _)| *scope != "palette") {
+            for key in ["fg", "bg"] {
+                if let Some(color) = style.get(key).and_then(|c| c.as_str()) {
+                    assert!(palette.contains_key(color), "{scope}.{key} = {color}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn diagnostics_use_curly_underlines() {
+        let (_, toml) = syntark();
+        assert!(toml.lines().any(|line| line
+            == r#""diagnostic.error" = { underline = { color = "error", style = "curl" } }"#));
+    }
+
+    #[test]
+    fn diff_foregrounds_are_readable() {
+        let (base, toml) = syntark();
+        let table = toml.parse::<toml::Table>().unwrap();
+        let palette = table["palette"].as_table().unwrap();
+        let background = base.category_color(Category::NormalBackground);
+        for scope in ["diff.plus", "diff.minus", "diff.delta"] {
+            let key = table[scope]["fg"].as_str().unwrap();
+            let color = crate::parse_color(palette[key].as_str().unwrap()).unwrap();
+            let ratio = crate::wcag_contrast_ratio(color, background);
+            assert!(ratio >= 3.0, "{scope}: {ratio}");
+        }
+    }
+}
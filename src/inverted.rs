@@ -0,0 +1,131 @@
+use crate::{
+    ensure_contrast, from_oklch, to_oklch, AnsiColor, Category, DiagnosticLevel, Style, Theme,
+    Token,
+};
+use palette::{Oklch, Srgb};
+
+#[derive(Clone, Debug)]
+pub struct Inverted<T> {
+    base: T,
+    background_lightness: f32,
+    min_contrast: f32,
+}
+
+impl<T> Inverted<T> {
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            background_lightness: 0.97,
+            min_contrast: 4.5,
+        }
+    }
+
+    pub fn background_lightness(self, lightness: f32) -> Self {
+        Self {
+            background_lightness: lightness,
+            ..self
+        }
+    }
+
+    pub fn min_contrast(self, ratio: f32) -> Self {
+        Self {
+            min_contrast: ratio,
+            ..self
+        }
+    }
+}
+
+impl<T: Theme> Inverted<T> {
+    // Inverts lightness, stretched so that the normal foreground keeps an inverted lightness and
+    // the normal background lands on `background_lightness`.
+    fn invert(&self, color: Srgb<u8>) -> Srgb<u8> {
+        let background = to_oklch(self.base.category_color(Category::NormalBackground)).l;
+        let foreground = to_oklch(self.base.category_color(Category::Normal)).l;
+        let scale = if (foreground - background).abs() < 1e-3 {
+            1.0
+        } else {
+            (self.background_lightness - 1.0 + foreground) / (foreground - background)
+        };
+        let offset = (1.0 - foreground) * (1.0 - scale);
+        let c = to_oklch(color);
+        from_oklch(Oklch {
+            l: scale * (1.0 - c.l) + offset,
+            ..c
+        })
+    }
+
+    fn readable(&self, color: Srgb<u8>) -> Srgb<u8> {
+        let background = self.category_color(Category::NormalBackground);
+        ensure_contrast(color, background, self.min_contrast)
+    }
+}
+
+impl<T: Theme> Theme for Inverted<T> {
+    fn category_color(&self, category: Category) -> Srgb<u8> {
+        let color = self.base.category_color(category);
+        match category {
+            Category::BadSpelling
+            | Category::MatchedBracket
+            | Category::Question
+            | Category::Special => self.readable(color),
+            _ => self.invert(color),
+        }
+    }
+
+    fn token_color(&self, token: Token) -> Srgb<u8> {
+        self.readable(self.base.token_color(token))
+    }
+
+    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
+        self.readable(self.base.diagnostic_level_color(level))
+    }
+
+    fn token_style(&self, token: Token) -> Style {
+        let style = self.base.token_style(token);
+        Style {
+            foreground: style.foreground.map(|c| self.readable(c)),
+            background: style.background.map(|c| self.invert(c)),
+            ..style
+        }
+    }
+
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        let base = self.base.ansi_color(color);
+        match color {
+            AnsiColor::Black
+            | AnsiColor::White
+            | AnsiColor::BrightBlack
+            | AnsiColor::BrightWhite => self.invert(base),
+            _ => self.readable(base),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wcag_contrast_ratio, SyntarkTheme};
+
+    #[test]
+    fn background_lands_on_requested_lightness() {
+        for lightness in [0.97, 0.9] {
+            let theme = Inverted::new(SyntarkTheme::default()).background_lightness(lightness);
+            let background = to_oklch(theme.category_color(Category::NormalBackground)).l;
+            assert!((background - lightness).abs() < 0.01, "{background}");
+        }
+    }
+
+    #[test]
+    fn tokens_stay_readable() {
+        let theme = Inverted::new(SyntarkTheme::default()).min_contrast(4.5);
+        let background = theme.category_color(Category::NormalBackground);
+        for token in enum_iterator::all::<Token>() {
+            let ratio = wcag_contrast_ratio(theme.token_color(token), background);
+            assert!(ratio >= 4.5, "{token:?}: {ratio}");
+        }
+        for level in enum_iterator::all::<DiagnosticLevel>() {
+            let ratio = wcag_contrast_ratio(theme.diagnostic_level_color(level), background);
+            assert!(ratio >= 4.5, "{level:?}: {ratio}");
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{
+    output::{Escaped, GENERATED_MARKER},
+    to_oklch, AnsiColor, Category, DiagnosticLevel, Style, Theme, Token, UnderlineStyle,
+};
+use palette::Srgb;
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JetBrainsTheme {
+    name: String,
+    parent_scheme: &'static str,
+    colors: Vec<(&'static str, Srgb<u8>)>,
+    attributes: Vec<(&'static str, Style)>,
+}
+
+impl JetBrainsTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        let parent_scheme = if to_oklch(base.category_color(Category::NormalBackground)).l <= 0.5 {
+            "Darcula"
+        } else {
+            "Default"
+        };
+        let colors = [
+            ("ADDED_LINES_COLOR", Category::DiffAdd),
+            ("CARET_COLOR", Category::TermCursor),
+            ("CARET_ROW_COLOR", Category::CursorLine),
+            ("CONSOLE_BACKGROUND_KEY", Category::NormalBackground),
+            ("DELETED_LINES_COLOR", Category::DiffDelete),
+            ("GUTTER_BACKGROUND", Category::NormalBackground),
+            ("INDENT_GUIDE", Category::ColumnGuide),
+            ("LINE_NUMBERS_COLOR", Category::LineNumber),
+            ("LINE_NUMBER_ON_CARET_ROW_COLOR", Category::CursorLineNumber),
+            ("METHOD_SEPARATORS_COLOR", Category::MessageSeparator),
+            ("MODIFIED_LINES_COLOR", Category::DiffChange),
+            ("RIGHT_MARGIN_COLOR", Category::ColumnGuide),
+            ("SELECTION_BACKGROUND", Category::Selection),
+            ("SOFT_WRAP_SIGN_COLOR", Category::NonText),
+            ("VISUAL_INDENT_GUIDE", Category::ColumnGuide),
+            ("WHITESPACES", Category::Whitespace),
+        ]
+        .into_iter()
+        .map(|(key, category)| (key, base.category_color(category)))
+        .collect();
+        Self {
+            name: name.into(),
+            parent_scheme,
+            colors,
+            attributes: attributes(base),
+        }
+    }
+
+    pub fn to_icls(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "<!-- {GENERATED_MARKER} -->")?;
+        writeln!(
+            writer,
+            r#"<scheme name="{}" version="142" parent_scheme="{}">"#,
+            Escaped(&self.name),
+            self.parent_scheme,
+        )?;
+        writeln!(writer, "  <colors>")?;
+        for &(key, color) in &self.colors {
+            writeln!(
+                writer,
+                r#"    <option name="{key}" value="{}"/>"#,
+                Hex(color)
+            )?;
+        }
+        writeln!(writer, "  </colors>")?;
+        writeln!(writer, "  <attributes>")?;
+        for (key, style) in &self.attributes {
+            writeln!(writer, r#"    <option name="{key}">"#)?;
+            writeln!(writer, "      <value>")?;
+            for (option, value) in attribute_options(style) {
+                writeln!(
+                    writer,
+                    r#"        <option name="{option}" value="{value}"/>"#
+                )?;
+            }
+            writeln!(writer, "      </value>")?;
+            writeln!(writer, "    </option>")?;
+        }
+        writeln!(writer, "  </attributes>")?;
+        writeln!(writer, "</scheme>")
+    }
+}
+
+struct Hex(Srgb<u8>);
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgb {
+            red, green, blue, ..
+        } = self.0;
+        write!(f, "{red:02x}{green:02x}{blue:02x}")
+    }
+}
+
+fn attributes<T: Theme + ?Sized>(base: &T) -> Vec<(&'static str, Style)> {
+    let category = |c| base.category_color(c);
+    let tokens = [
+        ("DEFAULT_BLOCK_COMMENT", Token::Comment),
+        ("DEFAULT_BRACES", Token::Delimiter),
+        ("DEFAULT_BRACKETS", Token::Delimiter),
+        ("DEFAULT_CLASS_NAME", Token::Type),
+        ("DEFAULT_COMMA", Token::Delimiter),
+        ("DEFAULT_CONSTANT", Token::Constant),
+        ("DEFAULT_DOC_COMMENT", Token::DocComment),
+        ("DEFAULT_DOT", Token::Delimiter),
+        ("DEFAULT_FUNCTION_CALL", Token::Function),
+        ("DEFAULT_FUNCTION_DECLARATION", Token::Function),
+        ("DEFAULT_IDENTIFIER", Token::Identifier),
+        ("DEFAULT_INSTANCE_FIELD", Token::Field),
+        ("DEFAULT_INTERFACE_NAME", Token::Interface),
+        ("DEFAULT_KEYWORD", Token::Keyword),
+        ("DEFAULT_LINE_COMMENT", Token::Comment),
+        ("DEFAULT_LOCAL_VARIABLE", Token::Variable),
+        ("DEFAULT_METADATA", Token::Attribute),
+        ("DEFAULT_NUMBER", Token::Integer),
+        ("DEFAULT_OPERATION_SIGN", Token::Operator),
+        ("DEFAULT_PARAMETER", Token::Parameter),
+        ("DEFAULT_PARENTHS", Token::Delimiter),
+        ("DEFAULT_SEMICOLON", Token::Delimiter),
+        ("DEFAULT_STATIC_FIELD", Token::Static),
+        ("DEFAULT_STRING", Token::String),
+        ("DEFAULT_TAG", Token::Tag),
+        ("HYPERLINK_ATTRIBUTES", Token::Link),
+        ("TODO_DEFAULT_ATTRIBUTES", Token::Todo),
+        ("org.rust.ATTRIBUTE", Token::Attribute),
+        ("org.rust.CONST_PARAMETER", Token::ConstGenericParameter),
+        ("org.rust.ENUM", Token::Enum),
+        ("org.rust.ENUM_VARIANT", Token::Variant),
+        ("org.rust.MACRO", Token::Macro),
+        ("org.rust.MODULE", Token::Module),
+        ("org.rust.STRUCT", Token::Struct),
+        ("org.rust.TRAIT", Token::Interface),
+        ("org.rust.TYPE_PARAMETER", Token::TypeParameter),
+    ]
+    .map(|(key, token)| (key, base.token_style(token)));
+    let ui = [
+        (
+            "TEXT",
+            Style::default()
+                .foreground(category(Category::Normal))
+                .background(category(Category::NormalBackground)),
+        ),
+        (
+            "DEFAULT_VALID_STRING_ESCAPE",
+            Style::default().foreground(category(Category::Special)),
+        ),
+        (
+            "DIFF_DELETED",
+            Style::default().background(category(Category::DiffDelete)),
+        ),
+        (
+            "DIFF_INSERTED",
+            Style::default().background(category(Category::DiffAdd)),
+        ),
+        (
+            "DIFF_MODIFIED",
+            Style::default().background(category(Category::DiffChange)),
+        ),
+        (
+            "FOLDED_TEXT_ATTRIBUTES",
+            Style::default().background(category(Category::Folded)),
+        ),
+        (
+            "MATCHED_BRACE_ATTRIBUTES",
+            Style::default()
+                .foreground(category(Category::MatchedBracket))
+                .bold(),
+        ),
+        (
+            "SEARCH_RESULT_ATTRIBUTES",
+            Style::default()
+                .foreground(category(Category::Search))
+                .background(category(Category::SearchMatch)),
+        ),
+        (
+            "TEXT_SEARCH_RESULT_ATTRIBUTES",
+            Style::default()
+                .foreground(category(Category::Search))
+                .background(category(Category::SearchMatch)),
+        ),
+        (
+            "TYPO",
+            Style::default()
+                .special(category(Category::BadSpelling))
+                .curly_underline(),
+        ),
+    ];
+    let diagnostics = [
+        ("ERRORS_ATTRIBUTES", DiagnosticLevel::Error),
+        ("WARNING_ATTRIBUTES", DiagnosticLevel::Warning),
+        ("INFO_ATTRIBUTES", DiagnosticLevel::Info),
+    ]
+    .map(|(key, level)| {
+        let style = Style::default()
+            .special(base.diagnostic_level_color(level))
+            .curly_underline();
+        (key, style)
+    });
+    let console = enum_iterator::all::<AnsiColor>().map(|color| {
+        let key = match color {
+            AnsiColor::Black => "CONSOLE_BLACK_OUTPUT",
+            AnsiColor::Red => "CONSOLE_RED_OUTPUT",
+            AnsiColor::Green => "CONSOLE_GREEN_OUTPUT",
+            AnsiColor::Yellow => "CONSOLE_YELLOW_OUTPUT",
+            AnsiColor::Blue => "CONSOLE_BLUE_OUTPUT",
+            AnsiColor::Magenta => "CONSOLE_MAGENTA_OUTPUT",
+            AnsiColor::Cyan => "CONSOLE_CYAN_OUTPUT",
+            AnsiColor::White => "CONSOLE_GRAY_OUTPUT",
+            AnsiColor::BrightBlack => "CONSOLE_DARKGRAY_OUTPUT",
+            AnsiColor::BrightRed => "CONSOLE_RED_BRIGHT_OUTPUT",
+            AnsiColor::BrightGreen => "CONSOLE_GREEN_BRIGHT_OUTPUT",
+            AnsiColor::BrightYellow => "CONSOLE_YELLOW_BRIGHT_OUTPUT",
+            AnsiColor::BrightBlue => "CONSOLE_BLUE_BRIGHT_OUTPUT",
+            AnsiColor::BrightMagenta => "CONSOLE_MAGENTA_BRIGHT_OUTPUT",
+            AnsiColor::BrightCyan => "CONSOLE_CYAN_BRIGHT_OUTPUT",
+            AnsiColor::BrightWhite => "CONSOLE_WHITE_OUTPUT",
+        };
+        (key, Style::default().foreground(base.ansi_color(color)))
+    });
+    tokens
+        .into_iter()
+        .chain(ui)
+        .chain(diagnostics)
+        .chain(console)
+        .collect()
+}
+
+fn attribute_options(style: &Style) -> Vec<(&'static str, String)> {
+    let mut options = Vec::new();
+    if let Some(c) = style.foreground {
+        options.push(("FOREGROUND", Hex(c).to_string()));
+    }
+    if let Some(c) = style.background {
+        options.push(("BACKGROUND", Hex(c).to_string()));
+    }
+    let font_type = match (style.bold.unwrap_or(false), style.italic.unwrap_or(false)) {
+        (false, false) => None,
+        (true, false) => Some(1),
+        (false, true) => Some(2),
+        (true, true) => Some(3),
+    };
+    if let Some(font_type) = font_type {
+        options.push(("FONT_TYPE", font_type.to_string()));
+    }
+    let effect_type = match style.underline {
+        Some(UnderlineStyle::Single | UnderlineStyle::Dashed) => Some(1),
+        Some(UnderlineStyle::Curly) => Some(2),
+        Some(UnderlineStyle::Double) => Some(4),
+        Some(UnderlineStyle::Dotted) => Some(5),
+        None => style.strikethrough.unwrap_or(false).then_some(3),
+    };
+    if let (Some(effect_type), Some(color)) = (effect_type, style.special.or(style.foreground)) {
+        options.push(("EFFECT_COLOR", Hex(color).to_string()));
+        options.push(("EFFECT_TYPE", effect_type.to_string()));
+    }
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Inverted, SyntarkTheme};
+
+    #[test]
+    fn scheme_header_is_escaped() {
+        let output = JetBrainsTheme::new("a<b>&\"c\"", &SyntarkTheme::default()).to_icls();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("<!-- Generated by syntinct -->"));
+        assert_eq!(
+            lines.next(),
+            Some(
+                r#"<scheme name="a&lt;b&gt;&amp;&quot;c&quot;" version="142" parent_scheme="Darcula">"#
+            )
+        );
+        assert!(output.ends_with("</scheme>\n"));
+    }
+
+    #[test]
+    fn parent_scheme_follows_the_background() {
+        let light = JetBrainsTheme::new("light", &Inverted::new(SyntarkTheme::default()));
+        assert_eq!(light.parent_scheme, "Default");
+    }
+
+    #[test]
+    fn attributes_are_written_as_options() {
+        let base = SyntarkTheme::default();
+        let output = JetBrainsTheme::new("syntark", &base).to_icls();
+        let expected = format!(
+            r#"    <option name="DEFAULT_KEYWORD">
+      <value>
+        <option name="FOREGROUND" value="{}"/>
+        <option name="FONT_TYPE" value="1"/>
+      </value>
+    </option>
+"#,
+            Hex(base.token_color(Token::Keyword))
+        );
+        assert!(output.contains(&expected), "{output}");
+    }
+
+    #[test]
+    fn underline_takes_precedence_over_strikethrough() {
+        let red = Srgb::new(0xff, 0, 0);
+        let style = Style::default()
+            .special(red)
+            .curly_underline()
+            .strikethrough();
+        assert_eq!(
+            attribute_options(&style),
+            [
+                ("EFFECT_COLOR", "ff0000".to_string()),
+                ("EFFECT_TYPE", "2".to_string())
+            ]
+        );
+        let style = Style::default().foreground(red).strikethrough();
+        assert_eq!(
+            attribute_options(&style),
+            [
+                ("FOREGROUND", "ff0000".to_string()),
+                ("EFFECT_COLOR", "ff0000".to_string()),
+                ("EFFECT_TYPE", "3".to_string())
+            ]
+        );
+    }
+}
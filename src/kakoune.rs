@@ -0,0 +1,214 @@
+use crate::{
+    output::GENERATED_MARKER, Category, DiagnosticLevel, Style, Theme, Token, UnderlineStyle,
+};
+use palette::Srgb;
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KakouneTheme {
+    name: String,
+    faces: Vec<(&'static str, Style)>,
+}
+
+impl KakouneTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            faces: faces(base),
+        }
+    }
+
+    pub fn to_kak(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "# {GENERATED_MARKER}")?;
+        writeln!(writer, "# {}", self.name)?;
+        writeln!(writer)?;
+        for (face, style) in &self.faces {
+            writeln!(writer, "face global {face} {}", face_spec(style))?;
+        }
+        Ok(())
+    }
+}
+
+fn faces<T: Theme + ?Sized>(base: &T) -> Vec<(&'static str, Style)> {
+    let category = |c| base.category_color(c);
+    let foreground = |c| Style::default().foreground(category(c));
+    let cursor = |c| {
+        Style::default()
+            .foreground(category(Category::NormalBackground))
+            .background(category(c))
+    };
+    let code = [
+        ("value", Token::Integer),
+        ("type", Token::Type),
+        ("variable", Token::Variable),
+        ("module", Token::Module),
+        ("function", Token::Function),
+        ("string", Token::String),
+        ("keyword", Token::Keyword),
+        ("operator", Token::Operator),
+        ("attribute", Token::Attribute),
+        ("comment", Token::Comment),
+        ("documentation", Token::DocComment),
+        ("meta", Token::Macro),
+        ("header", Token::Function),
+        ("mono", Token::String),
+        ("block", Token::String),
+        ("link", Token::Link),
+        ("bullet", Token::Operator),
+        ("list", Token::Operator),
+    ]
+    .map(|(face, token)| (face, base.token_style(token)));
+    let ui = [
+        ("builtin", foreground(Category::Special)),
+        (
+            "title",
+            base.token_style(Token::Keyword).no_background().bold(),
+        ),
+        (
+            "Default",
+            foreground(Category::Normal).background(category(Category::NormalBackground)),
+        ),
+        (
+            "PrimarySelection",
+            foreground(Category::Normal).background(category(Category::Selection)),
+        ),
+        (
+            "SecondarySelection",
+            foreground(Category::Search).background(category(Category::SearchMatch)),
+        ),
+        ("PrimaryCursor", cursor(Category::TermCursor)),
+        ("SecondaryCursor", cursor(Category::UnfocusedTermCursor)),
+        ("PrimaryCursorEol", cursor(Category::TermCursor)),
+        ("SecondaryCursorEol", cursor(Category::UnfocusedTermCursor)),
+        ("LineNumbers", foreground(Category::LineNumber)),
+        (
+            "LineNumberCursor",
+            foreground(Category::CursorLineNumber).background(category(Category::CursorLine)),
+        ),
+        ("LineNumbersWrapped", foreground(Category::NonText)),
+        (
+            "MenuForeground",
+            foreground(Category::Normal).background(category(Category::Selection)),
+        ),
+        (
+            "MenuBackground",
+            foreground(Category::Normal).background(category(Category::StatusLine)),
+        ),
+        ("MenuInfo", foreground(Category::InactiveTab)),
+        (
+            "Information",
+            foreground(Category::Normal).background(category(Category::StatusLine)),
+        ),
+        (
+            "Error",
+            Style::default().foreground(base.diagnostic_level_color(DiagnosticLevel::Error)),
+        ),
+        (
+            "StatusLine",
+            foreground(Category::ActiveTab).background(category(Category::StatusLine)),
+        ),
+        ("StatusLineMode", base.token_style(Token::Keyword)),
+        ("StatusLineInfo", base.token_style(Token::Function)),
+        ("StatusLineValue", base.token_style(Token::Integer)),
+        ("StatusCursor", cursor(Category::TermCursor)),
+        ("Prompt", foreground(Category::Question)),
+        ("MatchingChar", foreground(Category::MatchedBracket).bold()),
+        ("Whitespace", foreground(Category::Whitespace)),
+        ("BufferPadding", foreground(Category::NonText)),
+        ("WrapMarker", foreground(Category::ColumnGuide)),
+    ];
+    let diagnostics = enum_iterator::all::<DiagnosticLevel>().map(|level| {
+        let face = match level {
+            DiagnosticLevel::Error => "DiagnosticError",
+            DiagnosticLevel::Warning => "DiagnosticWarning",
+            DiagnosticLevel::Info => "DiagnosticInfo",
+            DiagnosticLevel::Hint => "DiagnosticHint",
+        };
+        let style = Style::default()
+            .special(base.diagnostic_level_color(level))
+            .curly_underline();
+        (face, style)
+    });
+    code.into_iter().chain(ui).chain(diagnostics).collect()
+}
+
+fn face_spec(style: &Style) -> String {
+    let color = |c: Option<Srgb<u8>>| match c {
+        Some(Srgb {
+            red, green, blue, ..
+        }) => format!("rgb:{red:02x}{green:02x}{blue:02x}"),
+        None => "default".into(),
+    };
+    let mut spec = format!("{},{}", color(style.foreground), color(style.background));
+    if style.underline.is_some() && style.special.is_some() {
+        spec.push(',');
+        spec.push_str(&color(style.special));
+    }
+    let underline = style.underline.map(|underline| match underline {
+        UnderlineStyle::Curly => 'c',
+        UnderlineStyle::Double => 'U',
+        UnderlineStyle::Single | UnderlineStyle::Dotted | UnderlineStyle::Dashed => 'u',
+    });
+    let attributes = [
+        style.bold.unwrap_or(false).then_some('b'),
+        style.italic.unwrap_or(false).then_some('i'),
+        underline,
+        style.strikethrough.unwrap_or(false).then_some('s'),
+        style.reversed.then_some('r'),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<String>();
+    if !attributes.is_empty() {
+        spec.push('+');
+        spec.push_str(&attributes);
+    }
+    spec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    #[test]
+    fn faces_are_declared_globally() {
+        let base = SyntarkTheme::default();
+        let output = KakouneTheme::new("syntark", &base).to_kak();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("# Generated by syntinct"));
+        assert_eq!(lines.next(), Some("# syntark"));
+        let Srgb {
+            red, green, blue, ..
+        } = base.token_color(Token::Keyword);
+        let expected = format!("face global keyword rgb:{red:02x}{green:02x}{blue:02x},default+b");
+        assert!(output.lines().any(|line| line == expected), "{output}");
+        assert!(output
+            .lines()
+            .skip(3)
+            .all(|line| line.starts_with("face global ")));
+    }
+
+    #[test]
+    fn face_specs_follow_kakoune_syntax() {
+        assert_eq!(face_spec(&Style::default()), "default,default");
+        let style = Style::default()
+            .foreground(Srgb::new(0x12, 0x34, 0x56))
+            .special(Srgb::new(0xff, 0, 0))
+            .curly_underline()
+            .bold();
+        assert_eq!(face_spec(&style), "rgb:123456,default,rgb:ff0000+bc");
+        let style = Style::reverse().dotted_underline().italic();
+        assert_eq!(face_spec(&style), "default,default+iur");
+    }
+}
@@ -1,15 +1,63 @@
 mod category;
 mod color;
+mod contrast;
+mod cvd;
+mod emacs;
+mod file_theme;
+mod generated;
+mod helix;
+mod inverted;
+mod jetbrains;
+mod kakoune;
+mod lsp;
 mod neovim;
+mod output;
+mod overrides;
+mod preview;
+mod quantize;
+mod sample;
 mod style;
+mod swatch;
 mod syntark;
+mod terminal;
+mod textmate;
 mod thematic;
 mod theme;
+mod vim;
+mod vscode;
+mod zed;
 
-pub use category::{Category, DiagnosticLevel, Token};
-pub use color::{darken, lighten};
+pub use category::{AnsiColor, Category, DiagnosticLevel, Token};
+pub use color::{
+    adjust_lightness, blend, darken, from_oklch, gamut_map, lighten, mix, parse_color, rotate_hue,
+    scale_chroma, to_oklch, with_lightness, ParseColorError,
+};
+pub use contrast::{
+    apca_contrast, check_contrast, ensure_contrast, wcag_contrast_ratio, ContrastCheck,
+    ContrastThresholds,
+};
+pub use cvd::{check_cvd, delta_e_2000, ColorVisionDeficiency, CvdConflict, DEFAULT_CVD_THRESHOLD};
+pub use emacs::EmacsTheme;
+pub use file_theme::{is_valid_theme_name, ColorEntry, FileTheme, FileThemeError};
+pub use generated::{GeneratedTheme, GeneratedThemeError};
+pub use helix::HelixTheme;
+pub use inverted::Inverted;
+pub use jetbrains::JetBrainsTheme;
+pub use kakoune::KakouneTheme;
 pub use neovim::NeovimTheme;
-pub use style::Style;
+pub use output::{
+    is_generated, neovim_colors_dir, neovim_colorscheme_path, write_generated_file, OutputError,
+};
+pub use overrides::{HighlightOverrides, OverridesError};
+pub use preview::write_preview;
+pub use quantize::{to_xterm_256, xterm_256_color, CtermPalette};
+pub use style::{Style, UnderlineStyle};
+pub use swatch::SwatchSheet;
 pub use syntark::SyntarkTheme;
-pub use thematic::ThematicTheme;
+pub use terminal::{AlacrittyTheme, KittyTheme, WezTermTheme};
+pub use textmate::TmTheme;
+pub use thematic::{Provider, ThematicPalette, ThematicPaletteError, ThematicTheme};
 pub use theme::Theme;
+pub use vim::VimTheme;
+pub use vscode::{write_vscode_extension, VsCodeTheme};
+pub use zed::ZedTheme;
@@ -0,0 +1,95 @@
+use crate::Token;
+use std::fmt::{self, Display};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
+pub(crate) enum LspType {
+    Class,
+    Const,
+    ConstParameter,
+    Decorator,
+    Derive,
+    Enum,
+    EnumMember,
+    Function,
+    Interface,
+    Keyword,
+    Macro,
+    Method,
+    Namespace,
+    Parameter,
+    Property,
+    SelfKeyword,
+    Static,
+    Struct,
+    Type,
+    TypeAlias,
+    TypeParameter,
+    Variable,
+}
+
+impl LspType {
+    #[allow(
+        clippy::inherent_to_string_shadow_display,
+        clippy::wrong_self_convention
+    )]
+    pub(crate) fn to_string(&self) -> String {
+        match self {
+            Self::Class => "class".into(),
+            Self::Const => "const".into(),
+            Self::ConstParameter => "constParameter".into(),
+            Self::Decorator => "decorator".into(),
+            Self::Derive => "derive".into(),
+            Self::Enum => "enum".into(),
+            Self::EnumMember => "enumMember".into(),
+            Self::Function => "function".into(),
+            Self::Interface => "interface".into(),
+            Self::Keyword => "keyword".into(),
+            Self::Macro => "macro".into(),
+            Self::Method => "method".into(),
+            Self::Namespace => "namespace".into(),
+            Self::Parameter => "parameter".into(),
+            Self::Property => "property".into(),
+            Self::SelfKeyword => "selfKeyword".into(),
+            Self::Static => "static".into(),
+            Self::Struct => "struct".into(),
+            Self::Type => "type".into(),
+            Self::TypeAlias => "typeAlias".into(),
+            Self::TypeParameter => "typeParameter".into(),
+            Self::Variable => "variable".into(),
+        }
+    }
+
+    /// Token whose style highlights the semantic token type.
+    pub(crate) fn token(self) -> Token {
+        match self {
+            Self::Class => Token::Type,
+            Self::Const => Token::Constant,
+            Self::ConstParameter => Token::ConstGenericParameter,
+            Self::Decorator => Token::Attribute,
+            Self::Derive => Token::Interface,
+            Self::Enum => Token::Enum,
+            Self::EnumMember => Token::Variant,
+            Self::Function => Token::Function,
+            Self::Interface => Token::Interface,
+            Self::Keyword => Token::Keyword,
+            Self::Macro => Token::Macro,
+            Self::Method => Token::Function,
+            Self::Namespace => Token::Module,
+            Self::Parameter => Token::Parameter,
+            Self::Property => Token::Field,
+            Self::SelfKeyword => Token::Keyword,
+            Self::Static => Token::Static,
+            Self::Struct => Token::Type,
+            Self::Type => Token::Type,
+            Self::TypeAlias => Token::Type,
+            Self::TypeParameter => Token::TypeParameter,
+            Self::Variable => Token::Variable,
+        }
+    }
+}
+
+impl Display for LspType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string())
+    }
+}
@@ -1,4 +1,11 @@
-use clap::{builder::styling::AnsiColor, Args, Parser, Subcommand, ValueEnum};
+use clap::{builder::styling::AnsiColor, ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use palette::Srgb;
+use std::{
+    error::Error,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use syntinct::{ContrastThresholds, CtermPalette, HighlightOverrides, Theme};
 
 fn terminal_styles() -> clap::builder::Styles {
     clap::builder::Styles::styled()
@@ -18,11 +25,193 @@ struct AppArgs {
 #[derive(Debug, Subcommand)]
 enum AppCommand {
     Generate(GenerateCommand),
+    Check(CheckCommand),
+    Cvd(CvdCommand),
+    Preview(PreviewCommand),
+    Swatch(SwatchCommand),
+    Install(InstallCommand),
+    VscodeExtension(VscodeExtensionCommand),
 }
 
 #[derive(Args, Debug)]
 struct GenerateCommand {
-    theme: ThemeName,
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Editor or terminal to generate the theme for
+    #[clap(long, default_value = "neovim")]
+    format: OutputFormat,
+    /// Variant to generate for formats that do not support both variants in one theme
+    #[clap(long, default_value = "dark")]
+    variant: Variant,
+    /// Palette of the cterm colors used by vim and neovim without true colors
+    #[clap(long, default_value = "256")]
+    cterm_colors: CtermColors,
+    /// TOML or JSON file overriding neovim highlight groups
+    #[clap(long)]
+    overrides: Option<PathBuf>,
+    /// Leave the background of the main neovim groups to the terminal
+    #[clap(long)]
+    transparent: bool,
+    /// File to write the theme to instead of standard output
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+    /// Overwrite the output file even if it was not generated by syntinct
+    #[clap(long)]
+    force: bool,
+}
+
+/// Install a neovim theme in the runtimepath
+#[derive(Args, Debug)]
+struct InstallCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Directory in the neovim runtimepath [default: $XDG_CONFIG_HOME/nvim]
+    #[clap(long)]
+    runtimepath: Option<PathBuf>,
+    /// Palette of the cterm colors used without true colors
+    #[clap(long, default_value = "256")]
+    cterm_colors: CtermColors,
+    /// TOML or JSON file overriding highlight groups
+    #[clap(long)]
+    overrides: Option<PathBuf>,
+    /// Leave the background of the main groups to the terminal
+    #[clap(long)]
+    transparent: bool,
+    /// Overwrite the theme file even if it was not generated by syntinct
+    #[clap(long)]
+    force: bool,
+}
+
+/// Package the dark and light variants as a VS Code extension
+#[derive(Args, Debug)]
+struct VscodeExtensionCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Directory to write the extension to
+    #[clap(long, short)]
+    output: PathBuf,
+    /// Overwrite the extension files even if they were not generated by syntinct
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum OutputFormat {
+    Neovim,
+    Vim,
+    Helix,
+    Vscode,
+    Tmtheme,
+    Zed,
+    Emacs,
+    Kakoune,
+    Icls,
+    Alacritty,
+    Kitty,
+    Wezterm,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CtermColors {
+    /// xterm-256 color cube and grayscale ramp
+    #[value(name = "256")]
+    Xterm256,
+    /// 16 colors of the theme terminal palette
+    #[value(name = "16")]
+    Ansi16,
+}
+
+impl From<CtermColors> for CtermPalette {
+    fn from(colors: CtermColors) -> Self {
+        match colors {
+            CtermColors::Xterm256 => Self::Xterm256,
+            CtermColors::Ansi16 => Self::Ansi16,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Variant {
+    Dark,
+    Light,
+}
+
+/// Check the contrast of theme colors
+#[derive(Args, Debug)]
+struct CheckCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Minimum WCAG 2.x contrast ratio
+    #[clap(long, default_value_t = ContrastThresholds::default().wcag_ratio)]
+    min_ratio: f32,
+    /// Minimum absolute APCA lightness contrast (Lc)
+    #[clap(long, default_value_t = ContrastThresholds::default().apca_lc)]
+    min_lc: f32,
+}
+
+/// Report theme colors that become hard to tell apart with color vision deficiencies
+#[derive(Args, Debug)]
+struct CvdCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// CIEDE2000 color difference below which two colors are considered indistinguishable
+    #[clap(long, default_value_t = syntinct::DEFAULT_CVD_THRESHOLD)]
+    threshold: f32,
+}
+
+/// Print a highlighted code sample to the terminal
+#[derive(Args, Debug)]
+struct PreviewCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Variant to preview
+    #[clap(long, default_value = "dark")]
+    variant: Variant,
+}
+
+/// Render the colors of a theme as a sheet of labelled swatches
+#[derive(Args, Debug)]
+struct SwatchCommand {
+    #[clap(flatten)]
+    theme: ThemeArgs,
+    /// Format of the swatch sheet
+    #[clap(long, default_value = "html")]
+    format: SwatchFormat,
+    /// File to write the swatch sheet to instead of standard output
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+    /// Overwrite the output file even if it was not generated by syntinct
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SwatchFormat {
+    Html,
+    Svg,
+}
+
+#[derive(Args, Debug)]
+#[clap(group(ArgGroup::new("source").required(true)))]
+struct ThemeArgs {
+    /// Built-in theme
+    #[clap(group = "source")]
+    theme: Option<ThemeName>,
+    /// TOML or JSON file defining a theme
+    #[clap(long, group = "source")]
+    theme_file: Option<PathBuf>,
+    /// TOML or JSON file defining the palette of a thematic theme
+    #[clap(long, group = "source")]
+    palette: Option<PathBuf>,
+    /// Background, foreground and accent colors to generate a theme from, separated by commas
+    #[clap(
+        long,
+        group = "source",
+        value_delimiter = ',',
+        value_name = "COLOR",
+        value_parser = syntinct::parse_color
+    )]
+    seed: Vec<Srgb<u8>>,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
@@ -31,25 +220,324 @@ enum ThemeName {
     Thematic,
 }
 
+struct ThemeBases {
+    name: String,
+    dark: Box<dyn Theme>,
+    light: Box<dyn Theme>,
+    single: bool,
+}
+
+impl ThemeBases {
+    fn titled_variants(&self) -> Vec<(String, &dyn Theme)> {
+        if self.single {
+            vec![(self.name.clone(), &*self.dark)]
+        } else {
+            vec![
+                (format!("{} (dark)", self.name), &*self.dark),
+                (format!("{} (light)", self.name), &*self.light),
+            ]
+        }
+    }
+
+    fn variant(&self, variant: Variant) -> &dyn Theme {
+        match variant {
+            Variant::Dark => &*self.dark,
+            Variant::Light => &*self.light,
+        }
+    }
+}
+
+impl ThemeArgs {
+    fn load(&self) -> Result<ThemeBases, Box<dyn Error>> {
+        if let Some(path) = &self.theme_file {
+            let theme = syntinct::FileTheme::from_path(path)?;
+            let name = match theme.name() {
+                Some(name) => name.into(),
+                None => theme_name_from_path(path, "set `name` in the theme file")?,
+            };
+            return Ok(ThemeBases {
+                name,
+                dark: Box::new(theme.clone()),
+                light: Box::new(theme),
+                single: true,
+            });
+        }
+        if let Some(path) = &self.palette {
+            let palette = syntinct::ThematicPalette::from_path(path)?;
+            return Ok(ThemeBases {
+                name: theme_name_from_path(path, "rename the palette file")?,
+                dark: Box::new(syntinct::ThematicTheme::from_provider(palette)),
+                light: Box::new(syntinct::ThematicTheme::from_provider(palette)),
+                single: true,
+            });
+        }
+        if !self.seed.is_empty() && self.seed.len() < 3 {
+            return Err("--seed requires a background, a foreground and accent colors".into());
+        }
+        if let [background, foreground, accents @ ..] = self.seed.as_slice() {
+            let theme = syntinct::GeneratedTheme::new(*background, *foreground, accents)?;
+            let inverted = syntinct::Inverted::new(theme.clone());
+            let bases = if syntinct::to_oklch(*background).l <= 0.5 {
+                ThemeBases {
+                    name: "generated".into(),
+                    dark: Box::new(theme),
+                    light: Box::new(inverted),
+                    single: false,
+                }
+            } else {
+                ThemeBases {
+                    name: "generated".into(),
+                    dark: Box::new(inverted.background_lightness(0.18)),
+                    light: Box::new(theme),
+                    single: false,
+                }
+            };
+            return Ok(bases);
+        }
+        let bases = match self.theme {
+            Some(ThemeName::Syntark) => ThemeBases {
+                name: "syntark".into(),
+                dark: Box::new(syntinct::SyntarkTheme::default()),
+                light: Box::new(syntinct::Inverted::new(syntinct::SyntarkTheme::default())),
+                single: false,
+            },
+            Some(ThemeName::Thematic) => ThemeBases {
+                name: "thematic".into(),
+                dark: Box::new(syntinct::ThematicTheme::dark()),
+                light: Box::new(syntinct::ThematicTheme::light()),
+                single: false,
+            },
+            None => return Err("No theme given".into()),
+        };
+        Ok(bases)
+    }
+}
+
+fn theme_name_from_path(path: &Path, hint: &str) -> Result<String, Box<dyn Error>> {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_else(|| "custom".into());
+    if syntinct::is_valid_theme_name(&name) {
+        Ok(name.into())
+    } else {
+        Err(format!(
+            "Invalid theme name `{name}` derived from {}; {hint}",
+            path.display()
+        )
+        .into())
+    }
+}
+
 fn main() {
     let args = AppArgs::parse();
-    match args.cmd {
+    let res = match args.cmd {
         AppCommand::Generate(cmd) => generate_theme(cmd),
+        AppCommand::Check(cmd) => check_theme(cmd),
+        AppCommand::Cvd(cmd) => check_cvd(cmd),
+        AppCommand::Preview(cmd) => preview_theme(cmd),
+        AppCommand::Swatch(cmd) => write_swatch(cmd),
+        AppCommand::Install(cmd) => install_theme(cmd),
+        AppCommand::VscodeExtension(cmd) => write_vscode_extension(cmd),
+    };
+    if let Err(e) = res {
+        report_error(&*e);
+        std::process::exit(1);
+    }
+}
+
+fn report_error(e: &dyn Error) {
+    eprintln!("Error: {e}");
+    let mut source = e.source();
+    while let Some(e) = source {
+        eprintln!("  Caused by: {e}");
+        source = e.source();
+    }
+}
+
+fn load_overrides(path: Option<&Path>) -> Result<HighlightOverrides, Box<dyn Error>> {
+    match path {
+        Some(path) => Ok(HighlightOverrides::from_path(path)?),
+        None => Ok(HighlightOverrides::default()),
+    }
+}
+
+fn generate_theme(cmd: GenerateCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let overrides = load_overrides(cmd.overrides.as_deref())?;
+    if cmd.overrides.is_some() && !matches!(cmd.format, OutputFormat::Neovim) {
+        return Err("--overrides is only supported with the neovim format".into());
+    }
+    if cmd.transparent && !matches!(cmd.format, OutputFormat::Neovim) {
+        return Err("--transparent is only supported with the neovim format".into());
+    }
+    let mut buffer = Vec::new();
+    match cmd.format {
+        OutputFormat::Neovim => {
+            syntinct::NeovimTheme::new(&bases.name, &*bases.dark, &*bases.light)
+                .cterm_palette(cmd.cterm_colors.into())
+                .transparent(cmd.transparent)
+                .overrides(&overrides)?
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Vim => {
+            syntinct::VimTheme::new(&bases.name, &*bases.dark, &*bases.light)
+                .cterm_palette(cmd.cterm_colors.into())
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Helix => {
+            syntinct::HelixTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Vscode => {
+            syntinct::VsCodeTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Tmtheme => {
+            syntinct::TmTheme::new(&bases.name, bases.variant(cmd.variant)).write(&mut buffer)?;
+        }
+        OutputFormat::Zed => {
+            let theme = if bases.single {
+                syntinct::ZedTheme::single(&bases.name, &*bases.dark)
+            } else {
+                syntinct::ZedTheme::new(&bases.name, &*bases.dark, &*bases.light)
+            };
+            theme.write(&mut buffer)?;
+        }
+        OutputFormat::Emacs => {
+            syntinct::EmacsTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Kakoune => {
+            syntinct::KakouneTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Icls => {
+            syntinct::JetBrainsTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Alacritty => {
+            syntinct::AlacrittyTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Kitty => {
+            syntinct::KittyTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
+        OutputFormat::Wezterm => {
+            syntinct::WezTermTheme::new(&bases.name, bases.variant(cmd.variant))
+                .write(&mut buffer)?;
+        }
     }
+    match &cmd.output {
+        Some(path) => syntinct::write_generated_file(path, &buffer, cmd.force)?,
+        None => std::io::stdout().write_all(&buffer)?,
+    }
+    Ok(())
 }
 
-fn generate_theme(cmd: GenerateCommand) {
-    let neovim_theme = match cmd.theme {
-        ThemeName::Syntark => syntinct::NeovimTheme::new(
-            "syntark",
-            &syntinct::SyntarkTheme::default(),
-            &syntinct::SyntarkTheme::default(),
-        ),
-        ThemeName::Thematic => syntinct::NeovimTheme::new(
-            "thematic",
-            &syntinct::ThematicTheme::dark(),
-            &syntinct::ThematicTheme::light(),
-        ),
+fn check_theme(cmd: CheckCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let thresholds = ContrastThresholds {
+        wcag_ratio: cmd.min_ratio,
+        apca_lc: cmd.min_lc,
     };
-    println!("{}", neovim_theme.to_lua_module());
+    let mut failures = 0;
+    let mut total = 0;
+    for (title, theme) in bases.titled_variants() {
+        println!("{title}");
+        for check in syntinct::check_contrast(theme) {
+            let passes = check.passes(&thresholds);
+            total += 1;
+            if !passes {
+                failures += 1;
+            }
+            println!(
+                "  {}  {:<40} {:>6.2}:1  Lc {:>6.1}",
+                if passes { "pass" } else { "FAIL" },
+                format!("{} on {:?}", check.foreground, check.background),
+                check.wcag_ratio,
+                check.apca_lc,
+            );
+        }
+    }
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(format!("{failures} of {total} contrast checks failed").into())
+    }
+}
+
+fn check_cvd(cmd: CvdCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let mut conflicts = 0;
+    for (title, theme) in bases.titled_variants() {
+        println!("{title}");
+        for conflict in syntinct::check_cvd(theme, cmd.threshold) {
+            conflicts += 1;
+            println!(
+                "  {:<13} {:<60} ΔE {:>5.1} (normal vision {:>5.1})",
+                conflict.deficiency.to_string(),
+                format!("{} vs {}", conflict.first, conflict.second),
+                conflict.simulated_delta_e,
+                conflict.delta_e,
+            );
+        }
+    }
+    if conflicts == 0 {
+        Ok(())
+    } else {
+        Err(format!("{conflicts} color pairs are hard to distinguish").into())
+    }
+}
+
+fn preview_theme(cmd: PreviewCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    syntinct::write_preview(std::io::stdout().lock(), bases.variant(cmd.variant))?;
+    Ok(())
+}
+
+fn write_swatch(cmd: SwatchCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let sheet = if bases.single {
+        syntinct::SwatchSheet::single(&bases.name, &*bases.dark)
+    } else {
+        syntinct::SwatchSheet::new(&bases.name, &*bases.dark, &*bases.light)
+    };
+    let mut buffer = Vec::new();
+    match cmd.format {
+        SwatchFormat::Html => sheet.write_html(&mut buffer)?,
+        SwatchFormat::Svg => sheet.write_svg(&mut buffer)?,
+    }
+    match &cmd.output {
+        Some(path) => syntinct::write_generated_file(path, &buffer, cmd.force)?,
+        None => std::io::stdout().write_all(&buffer)?,
+    }
+    Ok(())
+}
+
+fn install_theme(cmd: InstallCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let colors_dir = syntinct::neovim_colors_dir(cmd.runtimepath.as_deref())?;
+    let path = syntinct::neovim_colorscheme_path(&colors_dir, &bases.name)?;
+    let mut buffer = Vec::new();
+    syntinct::NeovimTheme::new(&bases.name, &*bases.dark, &*bases.light)
+        .cterm_palette(cmd.cterm_colors.into())
+        .transparent(cmd.transparent)
+        .overrides(&load_overrides(cmd.overrides.as_deref())?)?
+        .write(&mut buffer)?;
+    syntinct::write_generated_file(&path, &buffer, cmd.force)?;
+    println!("Installed {}", path.display());
+    Ok(())
+}
+
+fn write_vscode_extension(cmd: VscodeExtensionCommand) -> Result<(), Box<dyn Error>> {
+    let bases = cmd.theme.load()?;
+    let themes = [("dark", &bases.dark), ("light", &bases.light)].map(|(variant, theme)| {
+        syntinct::VsCodeTheme::new(format!("{} {variant}", bases.name), &**theme)
+    });
+    for path in syntinct::write_vscode_extension(&cmd.output, &bases.name, &themes, cmd.force)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
 }
@@ -1,5 +1,12 @@
 use crate::{
-    darken, lighten, style::UnderlineStyle, Category, DiagnosticLevel, Style, Theme, Token,
+    adjust_lightness, blend,
+    color::CssColor,
+    lsp::LspType,
+    output::GENERATED_MARKER,
+    overrides::{HighlightOverride, HighlightOverrides, OverridesError},
+    quantize::CtermPalette,
+    style::UnderlineStyle,
+    to_oklch, with_lightness, AnsiColor, Category, DiagnosticLevel, Style, Theme, Token,
 };
 use heck::ToUpperCamelCase;
 use palette::Srgb;
@@ -81,6 +88,7 @@ enum HighlightName {
     WinBar,
     WinBarNC,
     Lsp(LspHighlightName),
+    TreeSitter(TreeSitterHighlightName),
     Boolean,
     Character,
     Comment,
@@ -140,6 +148,11 @@ enum HighlightName {
 }
 
 impl HighlightName {
+    // Tree-sitter captures and LSP semantic tokens are only understood by Neovim.
+    fn is_neovim_only(&self) -> bool {
+        matches!(self, Self::Lsp(_) | Self::TreeSitter(_))
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     fn to_string(&self) -> String {
         match self {
@@ -212,6 +225,7 @@ impl HighlightName {
             Self::WinBar => "WinBar".into(),
             Self::WinBarNC => "WinBarNC".into(),
             Self::Lsp(h) => h.to_string(),
+            Self::TreeSitter(h) => h.to_string(),
             Self::Boolean => "Boolean".into(),
             Self::Character => "Character".into(),
             Self::Comment => "Comment".into(),
@@ -281,6 +295,15 @@ impl From<LspTypeMod> for HighlightName {
     }
 }
 
+impl From<TreeSitterCapture> for HighlightName {
+    fn from(capture: TreeSitterCapture) -> Self {
+        Self::TreeSitter(TreeSitterHighlightName {
+            capture,
+            lang: None,
+        })
+    }
+}
+
 impl From<LspType> for HighlightName {
     fn from(ty: LspType) -> Self {
         LspTypeMod::Type(ty).into()
@@ -345,86 +368,257 @@ impl Display for LspTypeMod {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-enum Language {
-    #[allow(dead_code)]
-    Rust,
+struct TreeSitterHighlightName {
+    capture: TreeSitterCapture,
+    lang: Option<Language>,
 }
 
-impl Language {
+impl TreeSitterHighlightName {
     #[allow(clippy::inherent_to_string_shadow_display)]
     fn to_string(&self) -> String {
-        match self {
-            Self::Rust => "rust".into(),
-        }
+        format!(
+            "@{}{}",
+            self.capture,
+            self.lang
+                .as_ref()
+                .map(|lang| format!(".{lang}"))
+                .unwrap_or_default()
+        )
     }
 }
 
-impl Display for Language {
+impl Display for TreeSitterHighlightName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&self.to_string())
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, enum_iterator::Sequence)]
-enum LspType {
-    Class,
-    Const,
-    ConstParameter,
-    Decorator,
-    Derive,
-    Enum,
-    EnumMember,
+enum TreeSitterCapture {
+    Variable,
+    VariableBuiltin,
+    VariableParameter,
+    VariableParameterBuiltin,
+    VariableMember,
+    Constant,
+    ConstantBuiltin,
+    ConstantMacro,
+    Module,
+    ModuleBuiltin,
+    Label,
+    String,
+    StringDocumentation,
+    StringRegexp,
+    StringEscape,
+    StringSpecial,
+    StringSpecialSymbol,
+    StringSpecialPath,
+    StringSpecialUrl,
+    Character,
+    CharacterSpecial,
+    Boolean,
+    Number,
+    NumberFloat,
+    Type,
+    TypeBuiltin,
+    TypeDefinition,
+    Attribute,
+    AttributeBuiltin,
+    Property,
     Function,
-    Interface,
+    FunctionBuiltin,
+    FunctionCall,
+    FunctionMacro,
+    FunctionMethod,
+    FunctionMethodCall,
+    Constructor,
+    Operator,
     Keyword,
-    Macro,
-    Method,
-    Namespace,
-    Parameter,
-    Property,
-    SelfKeyword,
-    Static,
-    Struct,
-    Type,
-    TypeAlias,
-    TypeParameter,
-    Variable,
+    KeywordCoroutine,
+    KeywordFunction,
+    KeywordOperator,
+    KeywordImport,
+    KeywordType,
+    KeywordModifier,
+    KeywordRepeat,
+    KeywordReturn,
+    KeywordDebug,
+    KeywordException,
+    KeywordConditional,
+    KeywordConditionalTernary,
+    KeywordDirective,
+    KeywordDirectiveDefine,
+    PunctuationDelimiter,
+    PunctuationBracket,
+    PunctuationSpecial,
+    Comment,
+    CommentDocumentation,
+    CommentError,
+    CommentWarning,
+    CommentTodo,
+    CommentNote,
+    MarkupStrong,
+    MarkupItalic,
+    MarkupStrikethrough,
+    MarkupUnderline,
+    MarkupHeading,
+    MarkupHeading1,
+    MarkupHeading2,
+    MarkupHeading3,
+    MarkupHeading4,
+    MarkupHeading5,
+    MarkupHeading6,
+    MarkupQuote,
+    MarkupMath,
+    MarkupLink,
+    MarkupLinkLabel,
+    MarkupLinkUrl,
+    MarkupRaw,
+    MarkupRawBlock,
+    MarkupList,
+    MarkupListChecked,
+    MarkupListUnchecked,
+    DiffPlus,
+    DiffMinus,
+    DiffDelta,
+    Tag,
+    TagBuiltin,
+    TagAttribute,
+    TagDelimiter,
 }
 
-impl LspType {
+impl TreeSitterCapture {
     #[allow(
         clippy::inherent_to_string_shadow_display,
         clippy::wrong_self_convention
     )]
     fn to_string(&self) -> String {
         match self {
-            Self::Class => "class".into(),
-            Self::Const => "const".into(),
-            Self::ConstParameter => "constParameter".into(),
-            Self::Decorator => "decorator".into(),
-            Self::Derive => "derive".into(),
-            Self::Enum => "enum".into(),
-            Self::EnumMember => "enumMember".into(),
+            Self::Variable => "variable".into(),
+            Self::VariableBuiltin => "variable.builtin".into(),
+            Self::VariableParameter => "variable.parameter".into(),
+            Self::VariableParameterBuiltin => "variable.parameter.builtin".into(),
+            Self::VariableMember => "variable.member".into(),
+            Self::Constant => "constant".into(),
+            Self::ConstantBuiltin => "constant.builtin".into(),
+            Self::ConstantMacro => "constant.macro".into(),
+            Self::Module => "module".into(),
+            Self::ModuleBuiltin => "module.builtin".into(),
+            Self::Label => "label".into(),
+            Self::String => "string".into(),
+            Self::StringDocumentation => "string.documentation".into(),
+            Self::StringRegexp => "string.regexp".into(),
+            Self::StringEscape => "string.escape".into(),
+            Self::StringSpecial => "string.special".into(),
+            Self::StringSpecialSymbol => "string.special.symbol".into(),
+            Self::StringSpecialPath => "string.special.path".into(),
+            Self::StringSpecialUrl => "string.special.url".into(),
+            Self::Character => "character".into(),
+            Self::CharacterSpecial => "character.special".into(),
+            Self::Boolean => "boolean".into(),
+            Self::Number => "number".into(),
+            Self::NumberFloat => "number.float".into(),
+            Self::Type => "type".into(),
+            Self::TypeBuiltin => "type.builtin".into(),
+            Self::TypeDefinition => "type.definition".into(),
+            Self::Attribute => "attribute".into(),
+            Self::AttributeBuiltin => "attribute.builtin".into(),
+            Self::Property => "property".into(),
             Self::Function => "function".into(),
-            Self::Interface => "interface".into(),
+            Self::FunctionBuiltin => "function.builtin".into(),
+            Self::FunctionCall => "function.call".into(),
+            Self::FunctionMacro => "function.macro".into(),
+            Self::FunctionMethod => "function.method".into(),
+            Self::FunctionMethodCall => "function.method.call".into(),
+            Self::Constructor => "constructor".into(),
+            Self::Operator => "operator".into(),
             Self::Keyword => "keyword".into(),
-            Self::Macro => "macro".into(),
-            Self::Method => "method".into(),
-            Self::Namespace => "namespace".into(),
-            Self::Parameter => "parameter".into(),
-            Self::Property => "property".into(),
-            Self::SelfKeyword => "selfKeyword".into(),
-            Self::Static => "static".into(),
-            Self::Struct => "struct".into(),
-            Self::Type => "type".into(),
-            Self::TypeAlias => "typeAlias".into(),
-            Self::TypeParameter => "typeParameter".into(),
-            Self::Variable => "variable".into(),
+            Self::KeywordCoroutine => "keyword.coroutine".into(),
+            Self::KeywordFunction => "keyword.function".into(),
+            Self::KeywordOperator => "keyword.operator".into(),
+            Self::KeywordImport => "keyword.import".into(),
+            Self::KeywordType => "keyword.type".into(),
+            Self::KeywordModifier => "keyword.modifier".into(),
+            Self::KeywordRepeat => "keyword.repeat".into(),
+            Self::KeywordReturn => "keyword.return".into(),
+            Self::KeywordDebug => "keyword.debug".into(),
+            Self::KeywordException => "keyword.exception".into(),
+            Self::KeywordConditional => "keyword.conditional".into(),
+            Self::KeywordConditionalTernary => "keyword.conditional.ternary".into(),
+            Self::KeywordDirective => "keyword.directive".into(),
+            Self::KeywordDirectiveDefine => "keyword.directive.define".into(),
+            Self::PunctuationDelimiter => "punctuation.delimiter".into(),
+            Self::PunctuationBracket => "punctuation.bracket".into(),
+            Self::PunctuationSpecial => "punctuation.special".into(),
+            Self::Comment => "comment".into(),
+            Self::CommentDocumentation => "comment.documentation".into(),
+            Self::CommentError => "comment.error".into(),
+            Self::CommentWarning => "comment.warning".into(),
+            Self::CommentTodo => "comment.todo".into(),
+            Self::CommentNote => "comment.note".into(),
+            Self::MarkupStrong => "markup.strong".into(),
+            Self::MarkupItalic => "markup.italic".into(),
+            Self::MarkupStrikethrough => "markup.strikethrough".into(),
+            Self::MarkupUnderline => "markup.underline".into(),
+            Self::MarkupHeading => "markup.heading".into(),
+            Self::MarkupHeading1 => "markup.heading.1".into(),
+            Self::MarkupHeading2 => "markup.heading.2".into(),
+            Self::MarkupHeading3 => "markup.heading.3".into(),
+            Self::MarkupHeading4 => "markup.heading.4".into(),
+            Self::MarkupHeading5 => "markup.heading.5".into(),
+            Self::MarkupHeading6 => "markup.heading.6".into(),
+            Self::MarkupQuote => "markup.quote".into(),
+            Self::MarkupMath => "markup.math".into(),
+            Self::MarkupLink => "markup.link".into(),
+            Self::MarkupLinkLabel => "markup.link.label".into(),
+            Self::MarkupLinkUrl => "markup.link.url".into(),
+            Self::MarkupRaw => "markup.raw".into(),
+            Self::MarkupRawBlock => "markup.raw.block".into(),
+            Self::MarkupList => "markup.list".into(),
+            Self::MarkupListChecked => "markup.list.checked".into(),
+            Self::MarkupListUnchecked => "markup.list.unchecked".into(),
+            Self::DiffPlus => "diff.plus".into(),
+            Self::DiffMinus => "diff.minus".into(),
+            Self::DiffDelta => "diff.delta".into(),
+            Self::Tag => "tag".into(),
+            Self::TagBuiltin => "tag.builtin".into(),
+            Self::TagAttribute => "tag.attribute".into(),
+            Self::TagDelimiter => "tag.delimiter".into(),
+        }
+    }
+}
+
+impl Display for TreeSitterCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string())
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Language {
+    Json,
+    Lua,
+    Rust,
+    Toml,
+    Vimdoc,
+    Yaml,
+}
+
+impl Language {
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    fn to_string(&self) -> String {
+        match self {
+            Self::Json => "json".into(),
+            Self::Lua => "lua".into(),
+            Self::Rust => "rust".into(),
+            Self::Toml => "toml".into(),
+            Self::Vimdoc => "vimdoc".into(),
+            Self::Yaml => "yaml".into(),
         }
     }
 }
 
-impl Display for LspType {
+impl Display for Language {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&self.to_string())
     }
@@ -432,7 +626,6 @@ impl Display for LspType {
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 enum LspModifier {
-    #[allow(dead_code)]
     Deprecated,
 }
 
@@ -531,20 +724,54 @@ pub struct NeovimTheme {
     name: String,
     dark_highlights: HashMap<HighlightName, Highlight>,
     light_highlights: HashMap<HighlightName, Highlight>,
+    dark_terminal_colors: Vec<Srgb<u8>>,
+    light_terminal_colors: Vec<Srgb<u8>>,
+    cterm_palette: CtermPalette,
 }
 
 impl NeovimTheme {
     pub fn new<S, D, L>(name: S, dark_base: &D, light_base: &L) -> Self
     where
         S: Into<String>,
-        D: Theme,
-        L: Theme,
+        D: Theme + ?Sized,
+        L: Theme + ?Sized,
     {
         Self {
             name: name.into(),
             dark_highlights: build_highlights(dark_base),
             light_highlights: build_highlights(light_base),
+            dark_terminal_colors: terminal_colors(dark_base),
+            light_terminal_colors: terminal_colors(light_base),
+            cterm_palette: CtermPalette::default(),
+        }
+    }
+
+    pub fn cterm_palette(self, palette: CtermPalette) -> Self {
+        Self {
+            cterm_palette: palette,
+            ..self
+        }
+    }
+
+    /// Drops the background of the main editor groups so the terminal background shows through.
+    /// Overrides applied afterwards can still set a background.
+    pub fn transparent(self, transparent: bool) -> Self {
+        if !transparent {
+            return self;
         }
+        Self {
+            dark_highlights: strip_backgrounds(self.dark_highlights),
+            light_highlights: strip_backgrounds(self.light_highlights),
+            ..self
+        }
+    }
+
+    pub fn overrides(self, overrides: &HighlightOverrides) -> Result<Self, OverridesError> {
+        Ok(Self {
+            dark_highlights: apply_overrides(self.dark_highlights, overrides)?,
+            light_highlights: apply_overrides(self.light_highlights, overrides)?,
+            ..self
+        })
     }
 
     pub fn to_lua_module(&self) -> String {
@@ -554,11 +781,32 @@ impl NeovimTheme {
     }
 
     pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "-- {GENERATED_MARKER}")?;
         writeln!(writer, "local highlights")?;
         writeln!(writer, r#"if vim.o.background == "light" then"#)?;
-        write_highlights(&mut writer, Indent::default().inc(), &self.light_highlights)?;
+        write_highlights(
+            &mut writer,
+            Indent::default().inc(),
+            &self.light_highlights,
+            |c| self.cterm_palette.quantize(c, &self.light_terminal_colors),
+        )?;
+        write_terminal_colors(
+            &mut writer,
+            Indent::default().inc(),
+            &self.light_terminal_colors,
+        )?;
         writeln!(writer, "else")?;
-        write_highlights(&mut writer, Indent::default().inc(), &self.dark_highlights)?;
+        write_highlights(
+            &mut writer,
+            Indent::default().inc(),
+            &self.dark_highlights,
+            |c| self.cterm_palette.quantize(c, &self.dark_terminal_colors),
+        )?;
+        write_terminal_colors(
+            &mut writer,
+            Indent::default().inc(),
+            &self.dark_terminal_colors,
+        )?;
         writeln!(writer, "end")?;
         writeln!(writer)?;
         writeln!(writer, r#"vim.g.colors_name = "{}""#, self.name)?;
@@ -568,19 +816,132 @@ impl NeovimTheme {
     }
 }
 
-#[derive(Debug)]
-struct CssColor(Srgb<u8>);
+const TRANSPARENT_GROUPS: &[HighlightName] = &[
+    HighlightName::CursorLineNr,
+    HighlightName::EndOfBuffer,
+    HighlightName::FoldColumn,
+    HighlightName::LineNr,
+    HighlightName::MsgArea,
+    HighlightName::Normal,
+    HighlightName::NormalFloat,
+    HighlightName::NormalNC,
+    HighlightName::SignColumn,
+    HighlightName::StatusLine,
+    HighlightName::StatusLineNC,
+    HighlightName::TabLine,
+    HighlightName::TabLineFill,
+];
 
-impl Display for CssColor {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Srgb {
-            red, green, blue, ..
-        } = self.0;
-        write!(f, "#{red:02x}{green:02x}{blue:02x}")
+fn strip_backgrounds(
+    mut highlights: HashMap<HighlightName, Highlight>,
+) -> HashMap<HighlightName, Highlight> {
+    for name in TRANSPARENT_GROUPS {
+        if let Some(Highlight::Value(style)) = highlights.get_mut(name) {
+            style.background = None;
+        }
     }
+    highlights
 }
 
-fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
+fn apply_overrides(
+    mut highlights: HashMap<HighlightName, Highlight>,
+    overrides: &HighlightOverrides,
+) -> Result<HashMap<HighlightName, Highlight>, OverridesError> {
+    let names = highlights
+        .keys()
+        .map(|name| (name.to_string(), name.clone()))
+        .collect::<HashMap<_, _>>();
+    for (group, o) in overrides.groups() {
+        let name = names
+            .get(group)
+            .ok_or_else(|| OverridesError::UnknownGroup(group.into()))?;
+        let highlight = match o {
+            HighlightOverride::Link(target) => {
+                let target =
+                    names
+                        .get(target)
+                        .ok_or_else(|| OverridesError::UnknownLinkTarget {
+                            group: group.into(),
+                            target: target.clone(),
+                        })?;
+                let mut next = Some(target);
+                while let Some(linked) = next {
+                    if linked == name {
+                        return Err(OverridesError::CyclicLink(group.into()));
+                    }
+                    next = match highlights.get(linked) {
+                        Some(Highlight::Link(linked)) => Some(linked),
+                        _ => None,
+                    };
+                }
+                Highlight::Link(target.clone())
+            }
+            HighlightOverride::Style(style) => {
+                Highlight::Value(style.apply(resolve_style(&highlights, name)))
+            }
+        };
+        highlights.insert(name.clone(), highlight);
+    }
+    Ok(highlights)
+}
+
+fn resolve_style(highlights: &HashMap<HighlightName, Highlight>, name: &HighlightName) -> Style {
+    match highlights.get(name) {
+        Some(Highlight::Value(style)) => style.clone(),
+        Some(Highlight::Link(linked)) => resolve_style(highlights, linked),
+        None => Style::default(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ClassicHighlight {
+    Value(Style),
+    Link(String),
+}
+
+// Highlight groups understood by classic Vim, sorted by name. Links to Neovim-only groups are
+// replaced by the style they resolve to.
+pub(crate) fn classic_highlights<T: Theme + ?Sized>(base: &T) -> Vec<(String, ClassicHighlight)> {
+    fn resolve<'a>(
+        highlights: &'a HashMap<HighlightName, Highlight>,
+        mut highlight: &'a Highlight,
+    ) -> Option<ClassicHighlight> {
+        loop {
+            match highlight {
+                Highlight::Link(name) if name.is_neovim_only() => {
+                    highlight = highlights.get(name)?
+                }
+                Highlight::Link(name) => return Some(ClassicHighlight::Link(name.to_string())),
+                Highlight::Value(style) => return Some(ClassicHighlight::Value(style.clone())),
+            }
+        }
+    }
+
+    let highlights = build_highlights(base);
+    let mut groups = highlights
+        .iter()
+        .filter(|(name, _)| !name.is_neovim_only())
+        .filter_map(|(name, highlight)| Some((name.to_string(), resolve(&highlights, highlight)?)))
+        .collect::<Vec<_>>();
+    groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+    groups
+}
+
+pub(crate) fn terminal_colors<T: Theme + ?Sized>(base: &T) -> Vec<Srgb<u8>> {
+    enum_iterator::all::<AnsiColor>()
+        .map(|color| base.ansi_color(color))
+        .collect()
+}
+
+fn towards_foreground<T: Theme + ?Sized>(base: &T, color: Srgb<u8>, delta: f32) -> Srgb<u8> {
+    if to_oklch(base.category_color(Category::NormalBackground)).l <= 0.5 {
+        adjust_lightness(color, delta)
+    } else {
+        adjust_lightness(color, -delta)
+    }
+}
+
+fn build_highlights<T: Theme + ?Sized>(base: &T) -> HashMap<HighlightName, Highlight> {
     vec![
         (
             HighlightName::ColorColumn,
@@ -652,7 +1013,11 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         (
             HighlightName::WinSeparator,
             Style::default()
-                .foreground(darken(base.category_color(Category::Normal), 0.95))
+                .foreground(towards_foreground(
+                    base,
+                    base.category_color(Category::Normal),
+                    -0.56,
+                ))
                 .into(),
         ),
         (
@@ -736,9 +1101,10 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         (
             HighlightName::PmenuSbar,
             Style::default()
-                .foreground(lighten(
+                .foreground(towards_foreground(
+                    base,
                     base.category_color(Category::NormalBackground),
-                    0.1,
+                    0.27,
                 ))
                 .into(),
         ),
@@ -781,7 +1147,10 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         (
             HighlightName::StatusLineNC,
             Style::default()
-                .background(darken(base.category_color(Category::StatusLine), 0.5))
+                .background({
+                    let status_line = base.category_color(Category::StatusLine);
+                    with_lightness(status_line, to_oklch(status_line).l * 0.8)
+                })
                 .into(),
         ),
         (
@@ -827,170 +1196,28 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         (HighlightName::WildMenu, HighlightName::PmenuSel.into()),
         (HighlightName::WinBar, HighlightName::TabLineSel.into()),
         (HighlightName::WinBarNC, HighlightName::TabLine.into()),
-        (
-            LspType::Class.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Type))
-                .into(),
-        ),
-        (
-            LspType::Const.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Constant))
-                .into(),
-        ),
-        (
-            LspType::ConstParameter.into(),
-            Style::default()
-                .foreground(base.token_color(Token::ConstGenericParameter))
-                .into(),
-        ),
-        (
-            LspType::Decorator.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Attribute))
-                .into(),
-        ),
-        (
-            LspType::Derive.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Interface))
-                .into(),
-        ),
-        (
-            LspType::Enum.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Enum))
-                .into(),
-        ),
-        (
-            LspType::EnumMember.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Variant))
-                .into(),
-        ),
-        (
-            LspType::Function.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Function))
-                .into(),
-        ),
-        (
-            LspType::Interface.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Interface))
-                .into(),
-        ),
-        (
-            LspType::Keyword.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Keyword))
-                .into(),
-        ),
-        (
-            LspType::Macro.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Macro))
-                .into(),
-        ),
-        (
-            LspType::Method.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Function))
-                .into(),
-        ),
-        (
-            LspType::Namespace.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Module))
-                .into(),
-        ),
-        (
-            LspType::Parameter.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Parameter))
-                .into(),
-        ),
-        (
-            LspType::Property.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Field))
-                .into(),
-        ),
-        (
-            LspType::SelfKeyword.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Keyword))
-                .into(),
-        ),
-        (
-            LspType::Static.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Static))
-                .into(),
-        ),
-        (
-            LspType::Struct.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Type))
-                .into(),
-        ),
-        (
-            LspType::Type.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Type))
-                .into(),
-        ),
-        (
-            LspType::TypeAlias.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Type))
-                .into(),
-        ),
-        (
-            LspType::TypeParameter.into(),
-            Style::default()
-                .foreground(base.token_color(Token::TypeParameter))
-                .into(),
-        ),
-        (
-            LspType::Variable.into(),
-            Style::default()
-                .foreground(base.token_color(Token::Variable))
-                .into(),
-        ),
         (
             HighlightName::Boolean,
-            Style::default()
-                .foreground(base.token_color(Token::Boolean))
-                .into(),
+            base.token_style(Token::Boolean).into(),
         ),
         (
             HighlightName::Character,
-            Style::default()
-                .foreground(base.token_color(Token::Character))
-                .into(),
+            base.token_style(Token::Character).into(),
         ),
         (
             HighlightName::Comment,
-            Style::default()
-                .foreground(base.token_color(Token::Comment))
-                .into(),
+            base.token_style(Token::Comment).into(),
         ),
         (HighlightName::Conditional, HighlightName::Keyword.into()),
         (
             HighlightName::Constant,
-            Style::default()
-                .foreground(base.token_color(Token::Constant))
-                .into(),
+            base.token_style(Token::Constant).into(),
         ),
         (HighlightName::Debug, Style::default().into()),
         (HighlightName::Define, HighlightName::Macro.into()),
         (
             HighlightName::Delimiter,
-            Style::default()
-                .foreground(base.token_color(Token::Delimiter))
-                .into(),
+            base.token_style(Token::Delimiter).into(),
         ),
         (
             HighlightName::Error,
@@ -999,54 +1226,32 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
                 .into(),
         ),
         (HighlightName::Exception, HighlightName::Keyword.into()),
-        (
-            HighlightName::Float,
-            Style::default()
-                .foreground(base.token_color(Token::Float))
-                .into(),
-        ),
+        (HighlightName::Float, base.token_style(Token::Float).into()),
         (
             HighlightName::Function,
-            Style::default()
-                .foreground(base.token_color(Token::Function))
-                .into(),
+            base.token_style(Token::Function).into(),
         ),
         (
             HighlightName::Identifier,
-            Style::default()
-                .foreground(base.token_color(Token::Identifier))
-                .into(),
+            base.token_style(Token::Identifier).into(),
         ),
         (
             HighlightName::Include,
-            Style::default()
-                .foreground(base.token_color(Token::Module))
-                .into(),
+            base.token_style(Token::Module).into(),
         ),
         (
             HighlightName::Keyword,
-            Style::default()
-                .foreground(base.token_color(Token::Keyword))
-                .into(),
+            base.token_style(Token::Keyword).into(),
         ),
         (HighlightName::Label, HighlightName::Keyword.into()),
-        (
-            HighlightName::Macro,
-            Style::default()
-                .foreground(base.token_color(Token::Macro))
-                .into(),
-        ),
+        (HighlightName::Macro, base.token_style(Token::Macro).into()),
         (
             HighlightName::Number,
-            Style::default()
-                .foreground(base.token_color(Token::Integer))
-                .into(),
+            base.token_style(Token::Integer).into(),
         ),
         (
             HighlightName::Operator,
-            Style::default()
-                .foreground(base.token_color(Token::Operator))
-                .into(),
+            base.token_style(Token::Operator).into(),
         ),
         (HighlightName::PreCondit, HighlightName::Macro.into()),
         (HighlightName::PreProc, HighlightName::Macro.into()),
@@ -1063,35 +1268,16 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         (HighlightName::StorageClass, HighlightName::Keyword.into()),
         (
             HighlightName::String,
-            Style::default()
-                .foreground(base.token_color(Token::String))
-                .into(),
+            base.token_style(Token::String).into(),
         ),
         (HighlightName::Structure, HighlightName::Type.into()),
-        (
-            HighlightName::Tag,
-            Style::default()
-                .foreground(base.token_color(Token::Tag))
-                .into(),
-        ),
-        (
-            HighlightName::Todo,
-            Style::default()
-                .foreground(base.token_color(Token::Todo))
-                .into(),
-        ),
-        (
-            HighlightName::Type,
-            Style::default()
-                .foreground(base.token_color(Token::Type))
-                .into(),
-        ),
+        (HighlightName::Tag, base.token_style(Token::Tag).into()),
+        (HighlightName::Todo, base.token_style(Token::Todo).into()),
+        (HighlightName::Type, base.token_style(Token::Type).into()),
         (HighlightName::Typedef, HighlightName::Type.into()),
         (
             HighlightName::Underlined,
-            Style::default()
-                .foreground(base.token_color(Token::Link))
-                .into(),
+            base.token_style(Token::Link).into(),
         ),
     ]
     .into_iter()
@@ -1103,9 +1289,13 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
                 Some(DiagnosticUiKind::Underline) => {
                     Style::default().special(color).curly_underline()
                 }
-                Some(DiagnosticUiKind::VirtualText) => Style::default()
-                    .foreground(color)
-                    .background(darken(color, 0.95)),
+                Some(DiagnosticUiKind::VirtualText) => {
+                    Style::default().foreground(color).background(blend(
+                        color,
+                        0.15,
+                        base.category_color(Category::NormalBackground),
+                    ))
+                }
             };
             (
                 HighlightName::Diagnostic(Diagnostic { level, kind }),
@@ -1114,28 +1304,29 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         }),
     )
     .chain([
-        (HighlightName::DiagnosticDeprecated, Style::default().into()),
+        (
+            HighlightName::DiagnosticDeprecated,
+            Style::default().strikethrough().into(),
+        ),
+        (
+            LspModifier::Deprecated.into(),
+            HighlightName::DiagnosticDeprecated.into(),
+        ),
         (
             HighlightName::DiagnosticUnnecessary,
             Style::default().into(),
         ),
         (
             HighlightName::MarkdownCode,
-            Style::default()
-                .foreground(base.token_color(Token::Identifier))
-                .into(),
+            base.token_style(Token::Identifier).into(),
         ),
         (
             HighlightName::MarkdownCodeBlock,
-            Style::default()
-                .foreground(base.token_color(Token::String))
-                .into(),
+            base.token_style(Token::String).into(),
         ),
         (
             HighlightName::MarkdownH1,
-            Style::default()
-                .foreground(base.token_color(Token::Module))
-                .into(),
+            base.token_style(Token::Module).into(),
         ),
         (HighlightName::MarkdownH2, HighlightName::MarkdownH1.into()),
         (
@@ -1144,15 +1335,11 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
         ),
         (
             HighlightName::MarkdownLinkText,
-            Style::default()
-                .foreground(base.token_color(Token::Link))
-                .into(),
+            base.token_style(Token::Link).into(),
         ),
         (
             HighlightName::TomlTable,
-            Style::default()
-                .foreground(base.token_color(Token::Module))
-                .into(),
+            base.token_style(Token::Module).into(),
         ),
         (
             HighlightName::CmpItemAbbrMatch,
@@ -1163,6 +1350,10 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
             HighlightName::Special.into(),
         ),
     ])
+    .chain(
+        enum_iterator::all::<LspType>()
+            .map(|lsp_type| (lsp_type.into(), base.token_style(lsp_type.token()).into())),
+    )
     .chain(enum_iterator::all::<LspType>().map(|lsp_type| {
         (
             HighlightName::CmpItemKind(lsp_type),
@@ -1180,13 +1371,182 @@ fn build_highlights<T: Theme>(base: &T) -> HashMap<HighlightName, Highlight> {
             HighlightName::FloatBorder.into(),
         ),
     ])
+    .chain(enum_iterator::all::<TreeSitterCapture>().map(|capture| {
+        (
+            HighlightName::from(capture),
+            tree_sitter_highlight(base, capture),
+        )
+    }))
+    .chain(
+        language_highlights(base)
+            .into_iter()
+            .map(|(capture, lang, highlight)| {
+                let name = HighlightName::TreeSitter(TreeSitterHighlightName {
+                    capture,
+                    lang: Some(lang),
+                });
+                (name, highlight)
+            }),
+    )
     .collect()
 }
 
+// Neovim falls back from `@capture.lang` to `@capture`, so only captures that a language uses
+// differently need a group of their own.
+fn language_highlights<T: Theme + ?Sized>(
+    base: &T,
+) -> Vec<(TreeSitterCapture, Language, Highlight)> {
+    vec![
+        (
+            TreeSitterCapture::Label,
+            Language::Rust,
+            base.token_style(Token::TypeParameter).into(),
+        ),
+        (
+            TreeSitterCapture::PunctuationSpecial,
+            Language::Rust,
+            base.token_style(Token::Attribute).into(),
+        ),
+        (
+            TreeSitterCapture::Constructor,
+            Language::Lua,
+            base.token_style(Token::Delimiter).into(),
+        ),
+        (
+            TreeSitterCapture::Label,
+            Language::Vimdoc,
+            base.token_style(Token::Tag).into(),
+        ),
+        (
+            TreeSitterCapture::Property,
+            Language::Toml,
+            base.token_style(Token::Keyword).into(),
+        ),
+        (
+            TreeSitterCapture::Property,
+            Language::Yaml,
+            base.token_style(Token::Keyword).into(),
+        ),
+        (
+            TreeSitterCapture::Property,
+            Language::Json,
+            base.token_style(Token::Keyword).into(),
+        ),
+    ]
+}
+
+fn tree_sitter_highlight<T: Theme + ?Sized>(base: &T, capture: TreeSitterCapture) -> Highlight {
+    let token = |token| base.token_style(token).into();
+    let category = |category| {
+        Style::default()
+            .foreground(base.category_color(category))
+            .into()
+    };
+    let diagnostic = |level| {
+        Style::default()
+            .foreground(base.diagnostic_level_color(level))
+            .into()
+    };
+    let link = |capture: TreeSitterCapture| HighlightName::from(capture).into();
+    match capture {
+        TreeSitterCapture::Variable => token(Token::Variable),
+        TreeSitterCapture::VariableBuiltin => token(Token::Keyword),
+        TreeSitterCapture::VariableParameter => token(Token::Parameter),
+        TreeSitterCapture::VariableParameterBuiltin => link(TreeSitterCapture::VariableParameter),
+        TreeSitterCapture::VariableMember => token(Token::Field),
+        TreeSitterCapture::Constant => token(Token::Constant),
+        TreeSitterCapture::ConstantBuiltin => token(Token::Constant),
+        TreeSitterCapture::ConstantMacro => token(Token::Macro),
+        TreeSitterCapture::Module => token(Token::Module),
+        TreeSitterCapture::ModuleBuiltin => link(TreeSitterCapture::Module),
+        TreeSitterCapture::Label => HighlightName::Label.into(),
+        TreeSitterCapture::String => token(Token::String),
+        TreeSitterCapture::StringDocumentation => token(Token::DocComment),
+        TreeSitterCapture::StringRegexp => category(Category::Special),
+        TreeSitterCapture::StringEscape => category(Category::Special),
+        TreeSitterCapture::StringSpecial => category(Category::Special),
+        TreeSitterCapture::StringSpecialSymbol => link(TreeSitterCapture::StringSpecial),
+        TreeSitterCapture::StringSpecialPath => link(TreeSitterCapture::StringSpecial),
+        TreeSitterCapture::StringSpecialUrl => base.token_style(Token::Link).underline().into(),
+        TreeSitterCapture::Character => token(Token::Character),
+        TreeSitterCapture::CharacterSpecial => category(Category::Special),
+        TreeSitterCapture::Boolean => token(Token::Boolean),
+        TreeSitterCapture::Number => token(Token::Integer),
+        TreeSitterCapture::NumberFloat => token(Token::Float),
+        TreeSitterCapture::Type => token(Token::Type),
+        TreeSitterCapture::TypeBuiltin => token(Token::Type),
+        TreeSitterCapture::TypeDefinition => token(Token::Type),
+        TreeSitterCapture::Attribute => token(Token::Attribute),
+        TreeSitterCapture::AttributeBuiltin => link(TreeSitterCapture::Attribute),
+        TreeSitterCapture::Property => token(Token::Field),
+        TreeSitterCapture::Function => token(Token::Function),
+        TreeSitterCapture::FunctionBuiltin => link(TreeSitterCapture::Function),
+        TreeSitterCapture::FunctionCall => link(TreeSitterCapture::Function),
+        TreeSitterCapture::FunctionMacro => token(Token::Macro),
+        TreeSitterCapture::FunctionMethod => link(TreeSitterCapture::Function),
+        TreeSitterCapture::FunctionMethodCall => link(TreeSitterCapture::FunctionMethod),
+        TreeSitterCapture::Constructor => token(Token::Variant),
+        TreeSitterCapture::Operator => token(Token::Operator),
+        TreeSitterCapture::Keyword => token(Token::Keyword),
+        TreeSitterCapture::KeywordCoroutine
+        | TreeSitterCapture::KeywordFunction
+        | TreeSitterCapture::KeywordImport
+        | TreeSitterCapture::KeywordType
+        | TreeSitterCapture::KeywordModifier
+        | TreeSitterCapture::KeywordRepeat
+        | TreeSitterCapture::KeywordReturn
+        | TreeSitterCapture::KeywordDebug
+        | TreeSitterCapture::KeywordException
+        | TreeSitterCapture::KeywordConditional => link(TreeSitterCapture::Keyword),
+        TreeSitterCapture::KeywordOperator => token(Token::Operator),
+        TreeSitterCapture::KeywordConditionalTernary => link(TreeSitterCapture::Operator),
+        TreeSitterCapture::KeywordDirective => token(Token::Macro),
+        TreeSitterCapture::KeywordDirectiveDefine => link(TreeSitterCapture::KeywordDirective),
+        TreeSitterCapture::PunctuationDelimiter => token(Token::Delimiter),
+        TreeSitterCapture::PunctuationBracket => token(Token::Delimiter),
+        TreeSitterCapture::PunctuationSpecial => category(Category::Special),
+        TreeSitterCapture::Comment => token(Token::Comment),
+        TreeSitterCapture::CommentDocumentation => token(Token::DocComment),
+        TreeSitterCapture::CommentError => diagnostic(DiagnosticLevel::Error),
+        TreeSitterCapture::CommentWarning => diagnostic(DiagnosticLevel::Warning),
+        TreeSitterCapture::CommentTodo => token(Token::Todo),
+        TreeSitterCapture::CommentNote => diagnostic(DiagnosticLevel::Info),
+        TreeSitterCapture::MarkupStrong => Style::default().bold().into(),
+        TreeSitterCapture::MarkupItalic => Style::default().italic().into(),
+        TreeSitterCapture::MarkupStrikethrough => Style::default().strikethrough().into(),
+        TreeSitterCapture::MarkupUnderline => Style::default().underline().into(),
+        TreeSitterCapture::MarkupHeading => token(Token::Module),
+        TreeSitterCapture::MarkupHeading1
+        | TreeSitterCapture::MarkupHeading2
+        | TreeSitterCapture::MarkupHeading3
+        | TreeSitterCapture::MarkupHeading4
+        | TreeSitterCapture::MarkupHeading5
+        | TreeSitterCapture::MarkupHeading6 => link(TreeSitterCapture::MarkupHeading),
+        TreeSitterCapture::MarkupQuote => token(Token::Comment),
+        TreeSitterCapture::MarkupMath => category(Category::Special),
+        TreeSitterCapture::MarkupLink => token(Token::Link),
+        TreeSitterCapture::MarkupLinkLabel => link(TreeSitterCapture::MarkupLink),
+        TreeSitterCapture::MarkupLinkUrl => link(TreeSitterCapture::StringSpecialUrl),
+        TreeSitterCapture::MarkupRaw => token(Token::Identifier),
+        TreeSitterCapture::MarkupRawBlock => token(Token::String),
+        TreeSitterCapture::MarkupList => token(Token::Delimiter),
+        TreeSitterCapture::MarkupListChecked => link(TreeSitterCapture::MarkupList),
+        TreeSitterCapture::MarkupListUnchecked => link(TreeSitterCapture::MarkupList),
+        TreeSitterCapture::DiffPlus => token(Token::String),
+        TreeSitterCapture::DiffMinus => diagnostic(DiagnosticLevel::Error),
+        TreeSitterCapture::DiffDelta => diagnostic(DiagnosticLevel::Warning),
+        TreeSitterCapture::Tag => token(Token::Tag),
+        TreeSitterCapture::TagBuiltin => link(TreeSitterCapture::Tag),
+        TreeSitterCapture::TagAttribute => token(Token::Field),
+        TreeSitterCapture::TagDelimiter => token(Token::Delimiter),
+    }
+}
+
 fn write_highlights<W>(
     writer: &mut W,
     indent: Indent,
     highlights: &HashMap<HighlightName, Highlight>,
+    cterm: impl Fn(Srgb<u8>) -> u8,
 ) -> io::Result<()>
 where
     W: Write,
@@ -1205,9 +1565,11 @@ where
             Highlight::Value(style) => {
                 if let Some(fg) = style.foreground {
                     writeln!(writer, r#"{field_indent}fg = "{}","#, CssColor(fg))?;
+                    writeln!(writer, "{field_indent}ctermfg = {},", cterm(fg))?;
                 }
                 if let Some(bg) = style.background {
                     writeln!(writer, r#"{field_indent}bg = "{}","#, CssColor(bg))?;
+                    writeln!(writer, "{field_indent}ctermbg = {},", cterm(bg))?;
                 }
                 if let Some(c) = style.special {
                     writeln!(writer, r#"{field_indent}sp = "{}","#, CssColor(c))?;
@@ -1246,6 +1608,20 @@ where
     Ok(())
 }
 
+fn write_terminal_colors<W>(writer: &mut W, indent: Indent, colors: &[Srgb<u8>]) -> io::Result<()>
+where
+    W: Write,
+{
+    for (i, &color) in colors.iter().enumerate() {
+        writeln!(
+            writer,
+            r#"{indent}vim.g.terminal_color_{i} = "{}""#,
+            CssColor(color)
+        )?;
+    }
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Indent(usize);
 
@@ -1263,3 +1639,140 @@ impl Display for Indent {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Inverted, SyntarkTheme, ThematicTheme};
+
+    #[test]
+    fn language_captures_override_the_generic_capture() {
+        let base = SyntarkTheme::default();
+        let highlights = build_highlights(&base);
+        for (capture, lang, _) in language_highlights(&base) {
+            let name = HighlightName::TreeSitter(TreeSitterHighlightName {
+                capture,
+                lang: Some(lang.clone()),
+            });
+            assert!(highlights.contains_key(&name), "{name}");
+            assert_ne!(
+                resolve_style(&highlights, &name),
+                resolve_style(&highlights, &capture.into()),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn language_captures_are_suffixed() {
+        let name = HighlightName::TreeSitter(TreeSitterHighlightName {
+            capture: TreeSitterCapture::Label,
+            lang: Some(Language::Rust),
+        });
+        assert_eq!(name.to_string(), "@label.rust");
+    }
+
+    #[test]
+    fn token_styles_reach_highlights() {
+        let base = SyntarkTheme::default();
+        let highlights = build_highlights(&base);
+        let keyword = resolve_style(&highlights, &HighlightName::Keyword);
+        assert_eq!(keyword.bold, Some(true));
+        let comment = resolve_style(&highlights, &HighlightName::Comment);
+        assert_eq!(comment.italic, Some(true));
+    }
+
+    #[test]
+    fn derived_shades_suit_light_backgrounds() {
+        let (black, white) = (Srgb::new(0, 0, 0), Srgb::new(0xff, 0xff, 0xff));
+        let themes: [Box<dyn Theme>; 2] = [
+            Box::new(ThematicTheme::light()),
+            Box::new(Inverted::new(SyntarkTheme::default())),
+        ];
+        for theme in &themes {
+            let highlights = build_highlights(&**theme);
+            let style = |name| resolve_style(&highlights, &name);
+            let background = theme.category_color(Category::NormalBackground);
+            for (shade, source) in [
+                (style(HighlightName::PmenuSbar).foreground, background),
+                (
+                    style(HighlightName::StatusLineNC).background,
+                    theme.category_color(Category::StatusLine),
+                ),
+                (
+                    style(HighlightName::WinSeparator).foreground,
+                    theme.category_color(Category::Normal),
+                ),
+            ] {
+                let shade = shade.unwrap();
+                assert_ne!(shade, black);
+                assert_ne!(shade, white);
+                assert_ne!(shade, source);
+            }
+            assert!(
+                to_oklch(style(HighlightName::PmenuSbar).foreground.unwrap()).l
+                    < to_oklch(background).l
+            );
+        }
+    }
+
+    #[test]
+    fn deprecated_is_struck_through() {
+        let highlights = build_highlights(&SyntarkTheme::default());
+        assert_eq!(
+            resolve_style(&highlights, &LspModifier::Deprecated.into()).strikethrough,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn terminal_colors_are_set_for_both_variants() {
+        let base = SyntarkTheme::default();
+        let output = NeovimTheme::new("syntark", &base, &base).to_lua_module();
+        let expected = format!(
+            r#"  vim.g.terminal_color_12 = "{}""#,
+            CssColor(base.ansi_color(AnsiColor::BrightBlue))
+        );
+        assert_eq!(output.lines().filter(|line| *line == expected).count(), 2);
+        for i in 0..16 {
+            let key = format!("vim.g.terminal_color_{i} ");
+            assert_eq!(output.matches(&key).count(), 2, "{key}");
+        }
+        assert!(!output.contains("vim.g.terminal_color_16"));
+    }
+
+    #[test]
+    fn overrides_win_over_transparency() {
+        let base = SyntarkTheme::default();
+        let overrides = HighlightOverrides::from_toml_str(r##"Normal.bg = "#102030""##).unwrap();
+        let theme = NeovimTheme::new("syntark", &base, &base)
+            .transparent(true)
+            .overrides(&overrides)
+            .unwrap();
+        let normal = resolve_style(&theme.dark_highlights, &HighlightName::Normal);
+        assert_eq!(normal.background, Some(Srgb::new(0x10, 0x20, 0x30)));
+        let line_nr = resolve_style(&theme.dark_highlights, &HighlightName::LineNr);
+        assert_eq!(line_nr.background, None);
+    }
+
+    #[test]
+    fn diff_captures_color_the_text() {
+        let base = SyntarkTheme::default();
+        let highlights = build_highlights(&base);
+        for (capture, color) in [
+            (TreeSitterCapture::DiffPlus, base.token_color(Token::String)),
+            (
+                TreeSitterCapture::DiffMinus,
+                base.diagnostic_level_color(DiagnosticLevel::Error),
+            ),
+            (
+                TreeSitterCapture::DiffDelta,
+                base.diagnostic_level_color(DiagnosticLevel::Warning),
+            ),
+        ] {
+            let style = resolve_style(&highlights, &capture.into());
+            assert_eq!(style.foreground, Some(color), "{capture:?}");
+            assert_eq!(style.background, None, "{capture:?}");
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::is_valid_theme_name;
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+pub(crate) const GENERATED_MARKER: &str = "Generated by syntinct";
+
+pub fn neovim_colors_dir(runtimepath: Option<&Path>) -> Result<PathBuf, OutputError> {
+    let runtimepath = match runtimepath {
+        Some(path) => path.to_owned(),
+        None => env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .ok_or(OutputError::NoConfigDir)?
+            .join("nvim"),
+    };
+    Ok(runtimepath.join("colors"))
+}
+
+pub fn neovim_colorscheme_path(colors_dir: &Path, name: &str) -> Result<PathBuf, OutputError> {
+    if !is_valid_theme_name(name) {
+        return Err(OutputError::InvalidThemeName(name.into()));
+    }
+    let path = colors_dir.join(format!("{name}.lua"));
+    if path.parent() != Some(colors_dir) {
+        return Err(OutputError::InvalidPath(path));
+    }
+    Ok(path)
+}
+
+pub fn is_generated(path: &Path) -> Result<bool, OutputError> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(source) => {
+            return Err(OutputError::Io {
+                path: path.into(),
+                source,
+            })
+        }
+    };
+    let mut first_line = String::new();
+    BufReader::new(file)
+        .read_line(&mut first_line)
+        .map_err(|source| OutputError::Io {
+            path: path.into(),
+            source,
+        })?;
+    Ok(first_line.contains(GENERATED_MARKER))
+}
+
+pub fn write_generated_file(path: &Path, contents: &[u8], force: bool) -> Result<(), OutputError> {
+    if !force && path.exists() && !is_generated(path)? {
+        return Err(OutputError::NotGenerated(path.into()));
+    }
+    let io_error = |source| OutputError::Io {
+        path: path.into(),
+        source,
+    };
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir).map_err(io_error)?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| OutputError::InvalidPath(path.into()))?;
+    let mut tmp_name = PathBuf::from(".");
+    tmp_name.as_mut_os_string().push(file_name);
+    tmp_name
+        .as_mut_os_string()
+        .push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = dir.join(tmp_name);
+    let res = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    res.map_err(io_error)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutputError {
+    #[error("Failed to determine the configuration directory; set XDG_CONFIG_HOME or HOME")]
+    NoConfigDir,
+    #[error("Invalid theme name `{0}` for an output file")]
+    InvalidThemeName(String),
+    #[error("Invalid output path {0}")]
+    InvalidPath(PathBuf),
+    #[error("Refusing to overwrite {0} which was not generated by syntinct")]
+    NotGenerated(PathBuf),
+    #[error("Failed to access {path}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+// Escapes text for HTML and XML documents.
+pub(crate) struct Escaped<'a>(pub(crate) &'a str);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                _ => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colorscheme_path_stays_in_colors_dir() {
+        let dir = Path::new("/config/nvim/colors");
+        assert_eq!(
+            neovim_colorscheme_path(dir, "syntark").unwrap(),
+            dir.join("syntark.lua")
+        );
+        for name in ["../init", "/etc/passwd", "a/b", "", "."] {
+            assert!(matches!(
+                neovim_colorscheme_path(dir, name),
+                Err(OutputError::InvalidThemeName(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            Escaped(r#"<a href="x">&</a>"#).to_string(),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn refuses_to_overwrite_files_not_generated() {
+        let dir = env::temp_dir().join(format!("syntinct-output-{}", std::process::id()));
+        let path = dir.join("theme.lua");
+        write_generated_file(&path, b"-- Generated by syntinct\n", false).unwrap();
+        assert!(is_generated(&path).unwrap());
+        fs::write(&path, "-- hand written\n").unwrap();
+        assert!(!is_generated(&path).unwrap());
+        assert!(matches!(
+            write_generated_file(&path, b"", false),
+            Err(OutputError::NotGenerated(_))
+        ));
+        write_generated_file(&path, b"forced", true).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"forced");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{color::parse_color, style::UnderlineStyle, ParseColorError, Style};
+use palette::Srgb;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HighlightOverrides {
+    groups: BTreeMap<String, HighlightOverride>,
+}
+
+impl HighlightOverrides {
+    pub fn from_path<P>(path: P) -> Result<Self, OverridesError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let read = || {
+            fs::read_to_string(path).map_err(|source| OverridesError::Io {
+                path: path.into(),
+                source,
+            })
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&read()?),
+            Some("json") => Self::from_json_str(&read()?),
+            _ => Err(OverridesError::UnknownFormat(path.into())),
+        }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, OverridesError> {
+        Self::from_document(toml::from_str(s)?)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, OverridesError> {
+        Self::from_document(serde_json::from_str(s)?)
+    }
+
+    pub(crate) fn groups(&self) -> impl Iterator<Item = (&str, &HighlightOverride)> {
+        self.groups.iter().map(|(group, o)| (group.as_str(), o))
+    }
+
+    fn from_document(doc: BTreeMap<String, GroupDocument>) -> Result<Self, OverridesError> {
+        let groups = doc
+            .into_iter()
+            .map(|(group, o)| {
+                let o = o.parse(&group)?;
+                Ok((group, o))
+            })
+            .collect::<Result<_, OverridesError>>()?;
+        Ok(Self { groups })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum HighlightOverride {
+    Link(String),
+    Style(StyleOverride),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct StyleOverride {
+    foreground: Option<Option<Srgb<u8>>>,
+    background: Option<Option<Srgb<u8>>>,
+    special: Option<Option<Srgb<u8>>>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    strikethrough: Option<bool>,
+    underline: Option<Option<UnderlineStyle>>,
+    reversed: Option<bool>,
+}
+
+impl StyleOverride {
+    pub(crate) fn apply(&self, style: Style) -> Style {
+        Style {
+            foreground: self.foreground.unwrap_or(style.foreground),
+            background: self.background.unwrap_or(style.background),
+            special: self.special.unwrap_or(style.special),
+            bold: self.bold.or(style.bold),
+            italic: self.italic.or(style.italic),
+            strikethrough: self.strikethrough.or(style.strikethrough),
+            underline: self.underline.unwrap_or(style.underline),
+            reversed: self.reversed.unwrap_or(style.reversed),
+            ..style
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OverridesError {
+    #[error("Failed to read overrides file {path}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Unknown overrides file format for {0}; expected a `.toml` or `.json` extension")]
+    UnknownFormat(PathBuf),
+    #[error("Failed to parse TOML overrides")]
+    Toml(#[from] toml::de::Error),
+    #[error("Failed to parse JSON overrides")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid color for highlight group `{group}`")]
+    InvalidColor {
+        group: String,
+        #[source]
+        source: ParseColorError,
+    },
+    #[error("Invalid underline `{value}` for highlight group `{group}`")]
+    InvalidUnderline { group: String, value: String },
+    #[error("Highlight group `{0}` cannot both link to another group and set attributes")]
+    LinkWithAttributes(String),
+    #[error("Unknown highlight group `{0}`")]
+    UnknownGroup(String),
+    #[error("Highlight group `{group}` links to unknown group `{target}`")]
+    UnknownLinkTarget { group: String, target: String },
+    #[error("Highlight group `{0}` links to itself through other groups")]
+    CyclicLink(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GroupDocument {
+    link: Option<String>,
+    fg: Option<String>,
+    bg: Option<String>,
+    sp: Option<String>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    strikethrough: Option<bool>,
+    underline: Option<UnderlineDocument>,
+    reverse: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum UnderlineDocument {
+    Enabled(bool),
+    Style(UnderlineStyle),
+    Other(String),
+}
+
+impl GroupDocument {
+    fn parse(self, group: &str) -> Result<HighlightOverride, OverridesError> {
+        let color = |value: Option<String>| {
+            value
+                .map(|value| match value.as_str() {
+                    "none" | "NONE" => Ok(None),
+                    _ => parse_color(&value).map(Some),
+                })
+                .transpose()
+                .map_err(|source| OverridesError::InvalidColor {
+                    group: group.into(),
+                    source,
+                })
+        };
+        let underline = self
+            .underline
+            .map(|underline| match underline {
+                UnderlineDocument::Enabled(enabled) => {
+                    Ok(enabled.then_some(UnderlineStyle::Single))
+                }
+                UnderlineDocument::Style(style) => Ok(Some(style)),
+                UnderlineDocument::Other(value) if value == "none" || value == "NONE" => Ok(None),
+                UnderlineDocument::Other(value) => Err(OverridesError::InvalidUnderline {
+                    group: group.into(),
+                    value,
+                }),
+            })
+            .transpose()?;
+        let style = StyleOverride {
+            foreground: color(self.fg)?,
+            background: color(self.bg)?,
+            special: color(self.sp)?,
+            bold: self.bold,
+            italic: self.italic,
+            strikethrough: self.strikethrough,
+            underline,
+            reversed: self.reverse,
+        };
+        match self.link {
+            Some(_) if style != StyleOverride::default() => {
+                Err(OverridesError::LinkWithAttributes(group.into()))
+            }
+            Some(target) => Ok(HighlightOverride::Link(target)),
+            None => Ok(HighlightOverride::Style(style)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style_override(s: &str) -> StyleOverride {
+        let overrides = HighlightOverrides::from_toml_str(s).unwrap();
+        let style = match overrides.groups().next().unwrap().1 {
+            HighlightOverride::Style(style) => style.clone(),
+            o => panic!("{o:?}"),
+        };
+        style
+    }
+
+    #[test]
+    fn styles_and_links_are_parsed() {
+        let overrides = HighlightOverrides::from_toml_str(
+            r##"
+            Normal = { fg = "#112233", bg = "none", bold = true }
+            "@lsp.type.macro" = { link = "Function" }
+            "##,
+        )
+        .unwrap();
+        let groups = overrides.groups().collect::<Vec<_>>();
+        assert_eq!(
+            groups[0],
+            (
+                "@lsp.type.macro",
+                &HighlightOverride::Link("Function".into())
+            )
+        );
+        let style = Style::default()
+            .foreground(Srgb::new(1, 1, 1))
+            .background(Srgb::new(2, 2, 2))
+            .italic();
+        let HighlightOverride::Style(o) = groups[1].1 else {
+            panic!("{:?}", groups[1]);
+        };
+        assert_eq!(
+            o.apply(style),
+            Style {
+                foreground: Some(Srgb::new(0x11, 0x22, 0x33)),
+                background: None,
+                bold: Some(true),
+                italic: Some(true),
+                ..Style::default()
+            }
+        );
+    }
+
+    #[test]
+    fn json_overrides_are_parsed() {
+        let overrides =
+            HighlightOverrides::from_json_str(r#"{"Comment": {"italic": true}}"#).unwrap();
+        assert_eq!(overrides.groups().count(), 1);
+    }
+
+    #[test]
+    fn underline_can_be_set_or_cleared() {
+        let curly = Style::default().curly_underline();
+        for (document, expected) in [
+            ("underline = \"dotted\"", Some(UnderlineStyle::Dotted)),
+            ("underline = true", Some(UnderlineStyle::Single)),
+            ("underline = false", None),
+            ("underline = \"none\"", None),
+            ("bold = true", Some(UnderlineStyle::Curly)),
+        ] {
+            let o = style_override(&format!("DiagnosticUnderlineError = {{ {document} }}"));
+            assert_eq!(o.apply(curly.clone()).underline, expected, "{document}");
+        }
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        assert!(matches!(
+            HighlightOverrides::from_toml_str(r#"Normal = { fg = "nope" }"#),
+            Err(OverridesError::InvalidColor { group, .. }) if group == "Normal"
+        ));
+        assert!(matches!(
+            HighlightOverrides::from_toml_str(r#"Normal = { underline = "wavy" }"#),
+            Err(OverridesError::InvalidUnderline { value, .. }) if value == "wavy"
+        ));
+        assert!(matches!(
+            HighlightOverrides::from_toml_str(r#"Normal = { link = "Comment", bold = true }"#),
+            Err(OverridesError::LinkWithAttributes(_))
+        ));
+        assert!(matches!(
+            HighlightOverrides::from_toml_str(r#"Normal = { colour = "red" }"#),
+            Err(OverridesError::Toml(_))
+        ));
+        assert!(matches!(
+            HighlightOverrides::from_path("overrides.yaml"),
+            Err(OverridesError::UnknownFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    sample::{span_style, Line, SAMPLES},
+    Style, Theme, UnderlineStyle,
+};
+use palette::Srgb;
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+const WIDTH: usize = 100;
+
+pub fn write_preview<W, T>(mut writer: W, theme: &T) -> Result<(), io::Error>
+where
+    W: Write,
+    T: Theme + ?Sized,
+{
+    for (i, (title, lines)) in SAMPLES.into_iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "{title}")?;
+        for (number, line) in lines.iter().enumerate() {
+            write_line(&mut writer, theme, number + 1, line)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_line<W, T>(writer: &mut W, theme: &T, number: usize, line: &Line) -> io::Result<()>
+where
+    W: Write,
+    T: Theme + ?Sized,
+{
+    let background = line.background(theme);
+    let gutter = Ansi::default()
+        .foreground(theme.category_color(line.line_number()))
+        .background(background);
+    write!(writer, "{gutter}{} {number:>3} ", line.kind.sign())?;
+    for &(style, text) in line.spans {
+        let ansi = Ansi::from(span_style(theme, style, background));
+        write!(writer, "{ansi}{text}{RESET}")?;
+    }
+    let padding = WIDTH.saturating_sub(line.len() + 6);
+    let fill = Ansi::default().background(background);
+    writeln!(writer, "{fill}{:padding$}{RESET}", "")
+}
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Debug, Default)]
+struct Ansi {
+    foreground: Option<Srgb<u8>>,
+    background: Option<Srgb<u8>>,
+    underline: Option<(UnderlineStyle, Option<Srgb<u8>>)>,
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+    reversed: bool,
+}
+
+impl Ansi {
+    fn foreground(self, color: Srgb<u8>) -> Self {
+        Self {
+            foreground: Some(color),
+            ..self
+        }
+    }
+
+    fn background(self, color: Srgb<u8>) -> Self {
+        Self {
+            background: Some(color),
+            ..self
+        }
+    }
+}
+
+impl From<Style> for Ansi {
+    fn from(style: Style) -> Self {
+        Self {
+            foreground: style.foreground,
+            background: style.background,
+            underline: style.underline.map(|underline| (underline, style.special)),
+            bold: style.bold.unwrap_or_default(),
+            italic: style.italic.unwrap_or_default(),
+            strikethrough: style.strikethrough.unwrap_or_default(),
+            reversed: style.reversed,
+        }
+    }
+}
+
+impl Display for Ansi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(Srgb {
+            red, green, blue, ..
+        }) = self.foreground
+        {
+            write!(f, "\x1b[38;2;{red};{green};{blue}m")?;
+        }
+        if let Some(Srgb {
+            red, green, blue, ..
+        }) = self.background
+        {
+            write!(f, "\x1b[48;2;{red};{green};{blue}m")?;
+        }
+        if let Some((style, color)) = self.underline {
+            let code = match style {
+                UnderlineStyle::Single => 1,
+                UnderlineStyle::Double => 2,
+                UnderlineStyle::Curly => 3,
+                UnderlineStyle::Dotted => 4,
+                UnderlineStyle::Dashed => 5,
+            };
+            write!(f, "\x1b[4:{code}m")?;
+            if let Some(Srgb {
+                red, green, blue, ..
+            }) = color
+            {
+                write!(f, "\x1b[58:2::{red}:{green}:{blue}m")?;
+            }
+        }
+        let flags = [
+            (self.bold, 1),
+            (self.italic, 3),
+            (self.reversed, 7),
+            (self.strikethrough, 9),
+        ];
+        for (_, code) in flags.into_iter().filter(|&(flag, _)| flag) {
+            write!(f, "\x1b[{code}m")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    fn strip_escapes(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn ansi_codes_match_style() {
+        let ansi = Ansi::from(Style::default().foreground(Srgb::new(1, 2, 3)).bold());
+        assert_eq!(ansi.to_string(), "\x1b[38;2;1;2;3m\x1b[1m");
+        let ansi = Ansi::from(
+            Style::default()
+                .special(Srgb::new(4, 5, 6))
+                .curly_underline(),
+        );
+        assert_eq!(ansi.to_string(), "\x1b[4:3m\x1b[58:2::4:5:6m");
+        assert_eq!(Ansi::default().to_string(), "");
+    }
+
+    #[test]
+    fn preview_shows_every_sample_line_padded() {
+        let mut buffer = Vec::new();
+        write_preview(&mut buffer, &SyntarkTheme::default()).unwrap();
+        let text = strip_escapes(&String::from_utf8(buffer).unwrap());
+        let (title, lines) = SAMPLES[0];
+        let mut output = text.lines();
+        assert_eq!(output.next(), Some(title));
+        for line in lines {
+            let code = line.spans.iter().map(|(_, s)| *s).collect::<String>();
+            let shown = output.next().unwrap();
+            assert!(shown.contains(&code), "{shown:?}");
+            assert_eq!(shown.chars().count(), WIDTH.max(line.len() + 6));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use palette::{convert::FromColorUnclamped, Oklab, Srgb};
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CtermPalette {
+    #[default]
+    Xterm256,
+    Ansi16,
+}
+
+impl CtermPalette {
+    pub(crate) fn quantize(self, c: Srgb<u8>, ansi_colors: &[Srgb<u8>]) -> u8 {
+        match self {
+            Self::Xterm256 => to_xterm_256(c),
+            Self::Ansi16 => nearest(c, ansi_colors.iter().copied()) as u8,
+        }
+    }
+}
+
+pub fn xterm_256_color(index: u8) -> Srgb<u8> {
+    const ANSI: [u32; 16] = [
+        0x000000, 0xcd0000, 0x00cd00, 0xcdcd00, 0x0000ee, 0xcd00cd, 0x00cdcd, 0xe5e5e5, 0x7f7f7f,
+        0xff0000, 0x00ff00, 0xffff00, 0x5c5cff, 0xff00ff, 0x00ffff, 0xffffff,
+    ];
+    match index {
+        0..=15 => ANSI[usize::from(index)].into(),
+        16..=231 => {
+            let i = usize::from(index - 16);
+            Srgb::new(
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        232..=255 => {
+            let level = 8 + 10 * (index - 232);
+            Srgb::new(level, level, level)
+        }
+    }
+}
+
+pub fn to_xterm_256(c: Srgb<u8>) -> u8 {
+    16 + nearest(c, (16..=255).map(xterm_256_color)) as u8
+}
+
+fn distance(a: Oklab, b: Oklab) -> f32 {
+    ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+}
+
+fn nearest<I>(c: Srgb<u8>, candidates: I) -> usize
+where
+    I: IntoIterator<Item = Srgb<u8>>,
+{
+    let oklab = |c: Srgb<u8>| Oklab::from_color_unclamped(c.into_format::<f32>());
+    let target = oklab(c);
+    candidates
+        .into_iter()
+        .map(|candidate| distance(target, oklab(candidate)))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_entries_match_xterm() {
+        assert_eq!(xterm_256_color(1), Srgb::new(0xcd, 0x00, 0x00));
+        assert_eq!(xterm_256_color(16), Srgb::new(0, 0, 0));
+        assert_eq!(xterm_256_color(196), Srgb::new(255, 0, 0));
+        assert_eq!(xterm_256_color(110), Srgb::new(135, 175, 215));
+        assert_eq!(xterm_256_color(231), Srgb::new(255, 255, 255));
+        assert_eq!(xterm_256_color(232), Srgb::new(8, 8, 8));
+        assert_eq!(xterm_256_color(255), Srgb::new(238, 238, 238));
+    }
+
+    #[test]
+    fn palette_colors_map_to_themselves() {
+        for index in 16..=255 {
+            let c = xterm_256_color(index);
+            assert_eq!(xterm_256_color(to_xterm_256(c)), c, "{index}");
+        }
+    }
+
+    #[test]
+    fn colors_map_to_the_nearest_entry() {
+        assert_eq!(to_xterm_256(Srgb::new(250, 2, 3)), 196);
+        assert_eq!(to_xterm_256(Srgb::new(0x1e, 0x1e, 0x1e)), 234);
+        assert_eq!(to_xterm_256(Srgb::new(0x46, 0x82, 0xb4)), 67);
+    }
+
+    #[test]
+    fn ansi16_picks_from_the_given_colors() {
+        let colors = [
+            Srgb::new(0, 0, 0),
+            Srgb::new(200, 0, 0),
+            Srgb::new(0, 0, 200),
+        ];
+        let palette = CtermPalette::Ansi16;
+        assert_eq!(palette.quantize(Srgb::new(180, 20, 20), &colors), 1);
+        assert_eq!(palette.quantize(Srgb::new(10, 10, 10), &colors), 0);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{blend, Category, DiagnosticLevel, Style, Theme, Token, UnderlineStyle};
+use palette::Srgb;
+use SpanStyle::{Normal, Token as T};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum SpanStyle {
+    Normal,
+    Token(Token),
+    Category(Category),
+    VirtualText(DiagnosticLevel),
+    Diagnostic(DiagnosticLevel, Token),
+    Selected(Token),
+    SearchMatch,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum LineKind {
+    Code,
+    CursorLine,
+    DiffAdd,
+    DiffChange,
+    DiffDelete,
+}
+
+impl LineKind {
+    pub(crate) fn background(self) -> Option<Category> {
+        match self {
+            Self::Code => None,
+            Self::CursorLine => Some(Category::CursorLine),
+            Self::DiffAdd => Some(Category::DiffAdd),
+            Self::DiffChange => Some(Category::DiffChange),
+            Self::DiffDelete => Some(Category::DiffDelete),
+        }
+    }
+
+    pub(crate) fn sign(self) -> char {
+        match self {
+            Self::Code | Self::CursorLine => ' ',
+            Self::DiffAdd => '+',
+            Self::DiffChange => '~',
+            Self::DiffDelete => '-',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Line {
+    pub(crate) kind: LineKind,
+    pub(crate) spans: &'static [(SpanStyle, &'static str)],
+}
+
+impl Line {
+    const fn code(spans: &'static [(SpanStyle, &'static str)]) -> Self {
+        Self {
+            kind: LineKind::Code,
+            spans,
+        }
+    }
+
+    const fn with_kind(kind: LineKind, spans: &'static [(SpanStyle, &'static str)]) -> Self {
+        Self { kind, spans }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.spans.iter().map(|(_, s)| s.chars().count()).sum()
+    }
+
+    pub(crate) fn background<T>(&self, theme: &T) -> Srgb<u8>
+    where
+        T: Theme + ?Sized,
+    {
+        theme.category_color(self.kind.background().unwrap_or(Category::NormalBackground))
+    }
+
+    pub(crate) fn line_number(&self) -> Category {
+        if self.kind == LineKind::CursorLine {
+            Category::CursorLineNumber
+        } else {
+            Category::LineNumber
+        }
+    }
+}
+
+// Style of a span drawn over `background`, with the colors a neovim buffer would show.
+pub(crate) fn span_style<T>(theme: &T, span: SpanStyle, background: Srgb<u8>) -> Style
+where
+    T: Theme + ?Sized,
+{
+    let base = Style::default().background(background);
+    let token_style = |token| {
+        let style = theme.token_style(token);
+        Style {
+            background: style.background.or(Some(background)),
+            ..style
+        }
+    };
+    match span {
+        SpanStyle::Normal => base.foreground(theme.category_color(Category::Normal)),
+        SpanStyle::Token(token) => token_style(token),
+        SpanStyle::Category(category) => base.foreground(theme.category_color(category)),
+        SpanStyle::VirtualText(level) => {
+            let color = theme.diagnostic_level_color(level);
+            base.foreground(color).background(blend(
+                color,
+                0.15,
+                theme.category_color(Category::NormalBackground),
+            ))
+        }
+        SpanStyle::Diagnostic(level, token) => Style {
+            underline: Some(UnderlineStyle::Curly),
+            ..token_style(token).special(theme.diagnostic_level_color(level))
+        },
+        SpanStyle::Selected(token) => {
+            token_style(token).background(theme.category_color(Category::Selection))
+        }
+        SpanStyle::SearchMatch => base
+            .foreground(theme.category_color(Category::Search))
+            .background(theme.category_color(Category::SearchMatch)),
+    }
+}
+
+#[rustfmt::skip]
+pub(crate) const RUST_SAMPLE: &[Line] = &[
+    Line::code(&[(T(Token::Keyword), "use"), (Normal, " "), (T(Token::Module), "std"), (T(Token::Delimiter), "::"), (T(Token::Module), "fmt"), (T(Token::Delimiter), ";")]),
+    Line::code(&[]),
+    Line::code(&[(T(Token::DocComment), "/// Point in a 2D plane.")]),
+    Line::code(&[(T(Token::Attribute), "#[derive(Debug, Default)]")]),
+    Line::code(&[(T(Token::Keyword), "pub struct"), (Normal, " "), (T(Token::Struct), "Point"), (T(Token::Delimiter), "<"), (T(Token::TypeParameter), "T"), (T(Token::Delimiter), "> {")]),
+    Line::code(&[(Normal, "    "), (T(Token::Field), "x"), (T(Token::Delimiter), ": "), (T(Token::TypeParameter), "T"), (T(Token::Delimiter), ",")]),
+    Line::code(&[(Normal, "    "), (T(Token::Field), "y"), (T(Token::Delimiter), ": "), (T(Token::TypeParameter), "T"), (T(Token::Delimiter), ",")]),
+    Line::code(&[(T(Token::Delimiter), "}")]),
+    Line::code(&[]),
+    Line::code(&[(T(Token::Keyword), "static"), (Normal, " "), (T(Token::Static), "ORIGIN"), (T(Token::Delimiter), ": "), (T(Token::Struct), "Point"), (T(Token::Delimiter), "<"), (T(Token::Type), "f64"), (T(Token::Delimiter), ">"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Struct), "Point"), (T(Token::Delimiter), " { "), (T(Token::Field), "x"), (T(Token::Delimiter), ": "), (T(Token::Float), "0.0"), (T(Token::Delimiter), ", "), (T(Token::Field), "y"), (T(Token::Delimiter), ": "), (T(Token::Float), "0.0"), (T(Token::Delimiter), " };")]),
+    Line::code(&[(T(Token::Keyword), "const"), (Normal, " "), (T(Token::Constant), "LIMIT"), (T(Token::Delimiter), ": "), (T(Token::Type), "usize"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Integer), "42"), (T(Token::Delimiter), ";")]),
+    Line::code(&[]),
+    Line::code(&[(T(Token::Keyword), "fn"), (Normal, " "), (T(Token::Function), "describe"), (T(Token::Delimiter), "("), (T(Token::Parameter), "p"), (T(Token::Delimiter), ": &"), (T(Token::Struct), "Point"), (T(Token::Delimiter), "<"), (T(Token::Type), "f64"), (T(Token::Delimiter), ">, "), (T(Token::Parameter), "verbose"), (T(Token::Delimiter), ": "), (T(Token::Type), "bool"), (T(Token::Delimiter), ") -> "), (T(Token::Enum), "Option"), (T(Token::Delimiter), "<"), (T(Token::Type), "String"), (T(Token::Delimiter), "> {")]),
+    Line::with_kind(LineKind::CursorLine, &[(Normal, "    "), (T(Token::Comment), "// "), (T(Token::Todo), "TODO"), (T(Token::Comment), ": handle "), (SpanStyle::SearchMatch, "NaN"), (T(Token::Comment), " coordinates")]),
+    Line::code(&[(Normal, "    "), (T(Token::Keyword), "if"), (Normal, " "), (T(Token::Parameter), "verbose"), (Normal, " "), (T(Token::Operator), "&&"), (Normal, " "), (T(Token::Parameter), "p"), (T(Token::Delimiter), "."), (T(Token::Field), "x"), (Normal, " "), (T(Token::Operator), ">"), (Normal, " "), (T(Token::Static), "ORIGIN"), (T(Token::Delimiter), "."), (T(Token::Field), "x"), (T(Token::Delimiter), " {")]),
+    Line::code(&[(Normal, "        "), (T(Token::Keyword), "let"), (Normal, " "), (T(Token::Variable), "c"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Character), "'x'"), (T(Token::Delimiter), ";")]),
+    Line::code(&[(Normal, "        "), (T(Token::Keyword), "return"), (Normal, " "), (T(Token::Variant), "Some"), (T(Token::Delimiter), "("), (SpanStyle::Selected(Token::Macro), "format!"), (SpanStyle::Selected(Token::Delimiter), "("), (SpanStyle::Selected(Token::String), "\"{c} = {}\""), (T(Token::Delimiter), ", "), (T(Token::Parameter), "p"), (T(Token::Delimiter), "."), (T(Token::Field), "x"), (T(Token::Delimiter), "));")]),
+    Line::code(&[(Normal, "    "), (T(Token::Delimiter), "}")]),
+    Line::code(&[(Normal, "    "), (T(Token::Variant), "None"), (Normal, " "), (T(Token::Comment), "// see "), (T(Token::Link), "https://example.com")]),
+    Line::code(&[(T(Token::Delimiter), "}")]),
+];
+
+#[rustfmt::skip]
+pub(crate) const DIFF_SAMPLE: &[Line] = &[
+    Line::code(&[(T(Token::Keyword), "fn"), (Normal, " "), (T(Token::Function), "main"), (T(Token::Delimiter), "() {")]),
+    Line::with_kind(LineKind::DiffDelete, &[(Normal, "    "), (T(Token::Keyword), "let"), (Normal, " "), (T(Token::Variable), "enabled"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Boolean), "false"), (T(Token::Delimiter), ";")]),
+    Line::with_kind(LineKind::DiffAdd, &[(Normal, "    "), (T(Token::Keyword), "let"), (Normal, " "), (T(Token::Variable), "enabled"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Boolean), "true"), (T(Token::Delimiter), ";")]),
+    Line::with_kind(LineKind::DiffChange, &[(Normal, "    "), (T(Token::Function), "run"), (T(Token::Delimiter), "("), (T(Token::Variable), "enabled"), (T(Token::Delimiter), ", "), (T(Token::Integer), "3"), (T(Token::Delimiter), ");")]),
+    Line::code(&[(T(Token::Delimiter), "}")]),
+];
+
+#[rustfmt::skip]
+pub(crate) const DIAGNOSTIC_SAMPLE: &[Line] = &[
+    Line::code(&[(T(Token::Keyword), "let"), (Normal, " "), (T(Token::Variable), "n"), (T(Token::Delimiter), ": "), (T(Token::Type), "u8"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (SpanStyle::Diagnostic(DiagnosticLevel::Error, Token::String), "\"3\""), (T(Token::Delimiter), ";"), (Normal, "  "), (SpanStyle::VirtualText(DiagnosticLevel::Error), "mismatched types")]),
+    Line::code(&[(T(Token::Keyword), "let"), (Normal, " "), (SpanStyle::Diagnostic(DiagnosticLevel::Warning, Token::Variable), "unused"), (Normal, " "), (T(Token::Operator), "="), (Normal, " "), (T(Token::Integer), "1"), (T(Token::Delimiter), ";"), (Normal, "  "), (SpanStyle::VirtualText(DiagnosticLevel::Warning), "unused variable")]),
+    Line::code(&[(SpanStyle::Diagnostic(DiagnosticLevel::Info, Token::Function), "helper"), (T(Token::Delimiter), "();"), (Normal, "  "), (SpanStyle::VirtualText(DiagnosticLevel::Info), "defined in another crate")]),
+    Line::code(&[(T(Token::Variable), "v"), (T(Token::Delimiter), "."), (SpanStyle::Diagnostic(DiagnosticLevel::Hint, Token::Function), "len"), (T(Token::Delimiter), "() "), (T(Token::Operator), "=="), (Normal, " "), (T(Token::Integer), "0"), (Normal, "  "), (SpanStyle::VirtualText(DiagnosticLevel::Hint), "consider is_empty()")]),
+    Line::code(&[(SpanStyle::Category(Category::BadSpelling), "teh"), (Normal, " "), (SpanStyle::Category(Category::MatchedBracket), "("), (Normal, "spelling"), (SpanStyle::Category(Category::MatchedBracket), ")")]),
+];
+
+pub(crate) const SAMPLES: [(&str, &[Line]); 3] = [
+    ("Rust", RUST_SAMPLE),
+    ("Diff", DIFF_SAMPLE),
+    ("Diagnostics", DIAGNOSTIC_SAMPLE),
+];
@@ -1,4 +1,5 @@
 use palette::Srgb;
+use serde::Deserialize;
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[non_exhaustive]
@@ -72,6 +73,27 @@ impl Style {
         }
     }
 
+    pub fn bold(self) -> Self {
+        Self {
+            bold: Some(true),
+            ..self
+        }
+    }
+
+    pub fn italic(self) -> Self {
+        Self {
+            italic: Some(true),
+            ..self
+        }
+    }
+
+    pub fn strikethrough(self) -> Self {
+        Self {
+            strikethrough: Some(true),
+            ..self
+        }
+    }
+
     pub fn no_underline(self) -> Self {
         Self {
             underline: None,
@@ -115,7 +137,8 @@ impl Style {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum UnderlineStyle {
     Single,
@@ -0,0 +1,766 @@
+use crate::{
+    apca_contrast,
+    color::CssColor,
+    output::{Escaped, GENERATED_MARKER},
+    sample::{span_style, Line, SAMPLES},
+    to_oklch, wcag_contrast_ratio, Category, ColorEntry, DiagnosticLevel, Style, Theme, Token,
+    UnderlineStyle,
+};
+use palette::Srgb;
+use std::io::{self, Write};
+
+const PADDING: f32 = 24.0;
+const TITLE_HEIGHT: f32 = 48.0;
+const HEADING_HEIGHT: f32 = 36.0;
+const SWATCH_COLUMNS: usize = 3;
+const SWATCH_WIDTH: f32 = 260.0;
+const SWATCH_HEIGHT: f32 = 48.0;
+const CHIP_SIZE: f32 = 36.0;
+const LINE_HEIGHT: f32 = 20.0;
+const CHAR_WIDTH: f32 = 7.8;
+const COLUMN_WIDTH: f32 = 2.0 * PADDING + SWATCH_COLUMNS as f32 * SWATCH_WIDTH;
+
+const HTML_STYLE: &str = "
+body { margin: 0; font-family: sans-serif; font-size: 13px; }
+h1 { font-size: 20px; margin: 0 0 16px; }
+h2 { font-size: 15px; margin: 24px 0 8px; }
+.variants { display: flex; }
+.variant { flex: 1; padding: 24px; }
+.swatches { display: grid; grid-template-columns: repeat(auto-fill, minmax(240px, 1fr)); gap: 8px; }
+.swatch { display: flex; align-items: center; gap: 8px; }
+.chip { flex: none; width: 36px; height: 36px; border: 1px solid rgba(128, 128, 128, 0.4); border-radius: 4px; display: flex; align-items: center; justify-content: center; }
+.details { font-family: monospace; opacity: 0.8; }
+.code { font-family: monospace; line-height: 20px; overflow-x: auto; }
+.line { white-space: pre; }
+";
+
+/// Sheet of labelled swatches for every color of a theme, with contrast values and highlighted
+/// code samples, showing the dark and light variants side by side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwatchSheet {
+    name: String,
+    variants: Vec<SwatchVariant>,
+}
+
+impl SwatchSheet {
+    pub fn new<S, D, L>(name: S, dark_base: &D, light_base: &L) -> Self
+    where
+        S: Into<String>,
+        D: Theme + ?Sized,
+        L: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            variants: vec![
+                SwatchVariant::new("dark", dark_base),
+                SwatchVariant::new("light", light_base),
+            ],
+        }
+    }
+
+    pub fn single<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        let title = if to_oklch(base.category_color(Category::NormalBackground)).l <= 0.5 {
+            "dark"
+        } else {
+            "light"
+        };
+        Self {
+            name: name.into(),
+            variants: vec![SwatchVariant::new(title, base)],
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write_html(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write_svg(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write_html<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let name = Escaped(&self.name);
+        writeln!(writer, "<!-- {GENERATED_MARKER} -->")?;
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>{name}</title>")?;
+        writeln!(writer, "<style>{HTML_STYLE}</style>")?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<div class=\"variants\">")?;
+        for variant in &self.variants {
+            writeln!(
+                writer,
+                "<section class=\"variant\" style=\"color: {}; background: {}\">",
+                CssColor(variant.foreground),
+                CssColor(variant.background),
+            )?;
+            writeln!(writer, "<h1>{name} ({})</h1>", variant.title)?;
+            for (title, swatches) in &variant.groups {
+                writeln!(writer, "<h2>{title}</h2>")?;
+                writeln!(writer, "<div class=\"swatches\">")?;
+                for swatch in swatches {
+                    write!(
+                        writer,
+                        "<div class=\"swatch\"><div class=\"chip\" style=\"background: {}\">",
+                        CssColor(swatch.color),
+                    )?;
+                    if let Some(text) = swatch.text {
+                        write!(
+                            writer,
+                            "<span style=\"color: {}\">Aa</span>",
+                            CssColor(text)
+                        )?;
+                    }
+                    writeln!(
+                        writer,
+                        "</div><div><div>{}</div><div class=\"details\">{}</div></div></div>",
+                        swatch.label,
+                        swatch.details(),
+                    )?;
+                }
+                writeln!(writer, "</div>")?;
+            }
+            for (title, lines) in &variant.samples {
+                writeln!(writer, "<h2>{title}</h2>")?;
+                writeln!(writer, "<div class=\"code\">")?;
+                for (i, line) in lines.iter().enumerate() {
+                    write!(
+                        writer,
+                        "<div class=\"line\" style=\"background: {}\"><span style=\"color: {}\">{} {:>3} </span>",
+                        CssColor(line.background),
+                        CssColor(line.gutter),
+                        line.sign,
+                        i + 1,
+                    )?;
+                    for span in &line.spans {
+                        write!(
+                            writer,
+                            "<span style=\"{}\">{}</span>",
+                            span.css(),
+                            Escaped(span.text),
+                        )?;
+                    }
+                    writeln!(writer, "</div>")?;
+                }
+                writeln!(writer, "</div>")?;
+            }
+            writeln!(writer, "</section>")?;
+        }
+        writeln!(writer, "</div>")?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+
+    pub fn write_svg<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let mut columns = Vec::new();
+        for (i, variant) in self.variants.iter().enumerate() {
+            let mut column = Vec::new();
+            let x = i as f32 * COLUMN_WIDTH;
+            let height = write_svg_column(&mut column, &self.name, variant, x)?;
+            columns.push((x, variant.background, height, column));
+        }
+        let width = COLUMN_WIDTH * self.variants.len() as f32;
+        let height = columns
+            .iter()
+            .map(|&(_, _, height, _)| height)
+            .fold(0.0, f32::max);
+        writeln!(writer, "<!-- {GENERATED_MARKER} -->")?;
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"13\" xml:space=\"preserve\">",
+        )?;
+        writeln!(writer, "<title>{}</title>", Escaped(&self.name))?;
+        for (x, background, _, column) in columns {
+            writeln!(
+                writer,
+                "<rect x=\"{x}\" y=\"0\" width=\"{COLUMN_WIDTH}\" height=\"{height}\" fill=\"{}\"/>",
+                CssColor(background),
+            )?;
+            writer.write_all(&column)?;
+        }
+        writeln!(writer, "</svg>")?;
+        Ok(())
+    }
+}
+
+// Writes one variant starting at `x` and returns the height it takes.
+fn write_svg_column<W>(
+    writer: &mut W,
+    name: &str,
+    variant: &SwatchVariant,
+    x: f32,
+) -> io::Result<f32>
+where
+    W: Write,
+{
+    let left = x + PADDING;
+    let foreground = CssColor(variant.foreground);
+    let mut y = PADDING;
+    writeln!(
+        writer,
+        "<text x=\"{left}\" y=\"{}\" fill=\"{foreground}\" font-size=\"20\" font-weight=\"bold\">{} ({})</text>",
+        y + 20.0,
+        Escaped(name),
+        variant.title,
+    )?;
+    y += TITLE_HEIGHT;
+    let heading = |writer: &mut W, y: f32, title: &str| {
+        writeln!(
+            writer,
+            "<text x=\"{left}\" y=\"{}\" fill=\"{foreground}\" font-size=\"15\" font-weight=\"bold\">{title}</text>",
+            y + 20.0,
+        )
+    };
+    for (title, swatches) in &variant.groups {
+        heading(writer, y, title)?;
+        y += HEADING_HEIGHT;
+        for (i, swatch) in swatches.iter().enumerate() {
+            let sx = left + (i % SWATCH_COLUMNS) as f32 * SWATCH_WIDTH;
+            let sy = y + (i / SWATCH_COLUMNS) as f32 * SWATCH_HEIGHT;
+            writeln!(
+                writer,
+                "<rect x=\"{sx}\" y=\"{sy}\" width=\"{CHIP_SIZE}\" height=\"{CHIP_SIZE}\" rx=\"4\" fill=\"{}\" stroke=\"#808080\" stroke-opacity=\"0.4\"/>",
+                CssColor(swatch.color),
+            )?;
+            if let Some(text) = swatch.text {
+                writeln!(
+                    writer,
+                    "<text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"middle\">Aa</text>",
+                    sx + CHIP_SIZE / 2.0,
+                    sy + CHIP_SIZE / 2.0 + 4.0,
+                    CssColor(text),
+                )?;
+            }
+            writeln!(
+                writer,
+                "<text x=\"{}\" y=\"{}\" fill=\"{foreground}\">{}</text>",
+                sx + CHIP_SIZE + 8.0,
+                sy + 14.0,
+                swatch.label,
+            )?;
+            writeln!(
+                writer,
+                "<text x=\"{}\" y=\"{}\" fill=\"{foreground}\" fill-opacity=\"0.8\" font-family=\"monospace\" font-size=\"11\">{}</text>",
+                sx + CHIP_SIZE + 8.0,
+                sy + 30.0,
+                swatch.details(),
+            )?;
+        }
+        y += swatches.len().div_ceil(SWATCH_COLUMNS) as f32 * SWATCH_HEIGHT;
+    }
+    for (title, lines) in &variant.samples {
+        heading(writer, y, title)?;
+        y += HEADING_HEIGHT;
+        for (i, line) in lines.iter().enumerate() {
+            writeln!(
+                writer,
+                "<rect x=\"{left}\" y=\"{y}\" width=\"{}\" height=\"{LINE_HEIGHT}\" fill=\"{}\"/>",
+                COLUMN_WIDTH - 2.0 * PADDING,
+                CssColor(line.background),
+            )?;
+            let baseline = y + 14.0;
+            let gutter = format!("{} {:>3} ", line.sign, i + 1);
+            write_svg_text(writer, left, baseline, &gutter, line.gutter, "")?;
+            let mut column = gutter.chars().count();
+            for span in &line.spans {
+                let sx = left + column as f32 * CHAR_WIDTH;
+                let width = span.len() as f32 * CHAR_WIDTH;
+                if span.background != line.background {
+                    writeln!(
+                        writer,
+                        "<rect x=\"{sx:.1}\" y=\"{y}\" width=\"{width:.1}\" height=\"{LINE_HEIGHT}\" fill=\"{}\"/>",
+                        CssColor(span.background),
+                    )?;
+                }
+                let mut attributes = String::new();
+                if span.bold {
+                    attributes.push_str(" font-weight=\"bold\"");
+                }
+                if span.italic {
+                    attributes.push_str(" font-style=\"italic\"");
+                }
+                write_svg_text(
+                    writer,
+                    sx,
+                    baseline,
+                    span.text,
+                    span.foreground,
+                    &attributes,
+                )?;
+                if let Some((style, color)) = span.underline {
+                    write_svg_underline(writer, sx, width, baseline + 3.0, style, color)?;
+                }
+                if span.strikethrough {
+                    write_svg_underline(
+                        writer,
+                        sx,
+                        width,
+                        baseline - 4.0,
+                        UnderlineStyle::Single,
+                        span.foreground,
+                    )?;
+                }
+                column += span.len();
+            }
+            y += LINE_HEIGHT;
+        }
+    }
+    Ok(y + PADDING)
+}
+
+fn write_svg_text<W>(
+    writer: &mut W,
+    x: f32,
+    y: f32,
+    text: &str,
+    color: Srgb<u8>,
+    attributes: &str,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    // `textLength` keeps the spans aligned on the grid whatever monospace font is used.
+    writeln!(
+        writer,
+        "<text x=\"{x:.1}\" y=\"{y}\" fill=\"{}\" font-family=\"monospace\" textLength=\"{:.1}\" lengthAdjust=\"spacingAndGlyphs\"{attributes}>{}</text>",
+        CssColor(color),
+        text.chars().count() as f32 * CHAR_WIDTH,
+        Escaped(text),
+    )
+}
+
+fn write_svg_underline<W>(
+    writer: &mut W,
+    x: f32,
+    width: f32,
+    y: f32,
+    style: UnderlineStyle,
+    color: Srgb<u8>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let color = CssColor(color);
+    match style {
+        UnderlineStyle::Curly => {
+            let points = (0..=(width / 2.0) as usize)
+                .map(|i| {
+                    let dy = if i % 2 == 0 { 0.0 } else { 2.0 };
+                    format!("{:.1},{:.1}", x + i as f32 * 2.0, y + dy)
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                writer,
+                "<polyline points=\"{points}\" fill=\"none\" stroke=\"{color}\"/>"
+            )
+        }
+        _ => {
+            let dash = match style {
+                UnderlineStyle::Dotted => " stroke-dasharray=\"1 2\"",
+                UnderlineStyle::Dashed => " stroke-dasharray=\"4 2\"",
+                _ => "",
+            };
+            writeln!(
+                writer,
+                "<line x1=\"{x:.1}\" y1=\"{y}\" x2=\"{:.1}\" y2=\"{y}\" stroke=\"{color}\"{dash}/>",
+                x + width,
+            )?;
+            if style == UnderlineStyle::Double {
+                writeln!(
+                    writer,
+                    "<line x1=\"{x:.1}\" y1=\"{}\" x2=\"{:.1}\" y2=\"{}\" stroke=\"{color}\"/>",
+                    y + 2.0,
+                    x + width,
+                    y + 2.0,
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SwatchVariant {
+    title: &'static str,
+    foreground: Srgb<u8>,
+    background: Srgb<u8>,
+    groups: Vec<(&'static str, Vec<Swatch>)>,
+    samples: Vec<(&'static str, Vec<SampleLine>)>,
+}
+
+impl SwatchVariant {
+    fn new<T>(title: &'static str, theme: &T) -> Self
+    where
+        T: Theme + ?Sized,
+    {
+        let foreground = theme.category_color(Category::Normal);
+        let background = theme.category_color(Category::NormalBackground);
+        // Background categories are measured against the normal foreground drawn over them, and
+        // every other color against the normal background.
+        let swatch = |entry: ColorEntry, label: String| {
+            let color = entry.color(theme);
+            let (fg, bg, text) = match entry {
+                ColorEntry::Category(category) if is_background(category) => {
+                    (foreground, color, Some(foreground))
+                }
+                _ => (color, background, None),
+            };
+            Swatch {
+                label,
+                color,
+                text,
+                wcag_ratio: wcag_contrast_ratio(fg, bg),
+                apca_lc: apca_contrast(fg, bg),
+            }
+        };
+        let groups = vec![
+            (
+                "Categories",
+                enum_iterator::all::<Category>()
+                    .map(|c| swatch(c.into(), format!("{c:?}")))
+                    .collect(),
+            ),
+            (
+                "Tokens",
+                enum_iterator::all::<Token>()
+                    .map(|t| swatch(t.into(), format!("{t:?}")))
+                    .collect(),
+            ),
+            (
+                "Diagnostic levels",
+                enum_iterator::all::<DiagnosticLevel>()
+                    .map(|l| swatch(l.into(), format!("{l:?}")))
+                    .collect(),
+            ),
+        ];
+        let samples = SAMPLES
+            .into_iter()
+            .map(|(title, lines)| {
+                let lines = lines
+                    .iter()
+                    .map(|line| SampleLine::new(theme, line))
+                    .collect();
+                (title, lines)
+            })
+            .collect();
+        Self {
+            title,
+            foreground,
+            background,
+            groups,
+            samples,
+        }
+    }
+}
+
+fn is_background(category: Category) -> bool {
+    matches!(
+        category,
+        Category::ActiveSearchMatch
+            | Category::ActiveTabBackground
+            | Category::ColumnGuide
+            | Category::CursorLine
+            | Category::DiffAdd
+            | Category::DiffChange
+            | Category::DiffDelete
+            | Category::DiffText
+            | Category::Folded
+            | Category::InactiveTabBackground
+            | Category::NormalBackground
+            | Category::SearchMatch
+            | Category::Selection
+            | Category::StatusLine
+    )
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Swatch {
+    label: String,
+    color: Srgb<u8>,
+    text: Option<Srgb<u8>>,
+    wcag_ratio: f32,
+    apca_lc: f32,
+}
+
+impl Swatch {
+    fn details(&self) -> String {
+        format!(
+            "{} {:.2}:1 Lc {:.1}",
+            CssColor(self.color),
+            self.wcag_ratio,
+            self.apca_lc,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SampleLine {
+    background: Srgb<u8>,
+    gutter: Srgb<u8>,
+    sign: char,
+    spans: Vec<Span>,
+}
+
+impl SampleLine {
+    fn new<T>(theme: &T, line: &Line) -> Self
+    where
+        T: Theme + ?Sized,
+    {
+        let background = line.background(theme);
+        let foreground = theme.category_color(Category::Normal);
+        let spans = line
+            .spans
+            .iter()
+            .map(|&(style, text)| {
+                Span::new(
+                    text,
+                    span_style(theme, style, background),
+                    foreground,
+                    background,
+                )
+            })
+            .collect();
+        Self {
+            background,
+            gutter: theme.category_color(line.line_number()),
+            sign: line.kind.sign(),
+            spans,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Span {
+    text: &'static str,
+    foreground: Srgb<u8>,
+    background: Srgb<u8>,
+    underline: Option<(UnderlineStyle, Srgb<u8>)>,
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+}
+
+impl Span {
+    fn new(
+        text: &'static str,
+        style: Style,
+        default_foreground: Srgb<u8>,
+        default_background: Srgb<u8>,
+    ) -> Self {
+        let mut foreground = style.foreground.unwrap_or(default_foreground);
+        let mut background = style.background.unwrap_or(default_background);
+        if style.reversed {
+            std::mem::swap(&mut foreground, &mut background);
+        }
+        Self {
+            text,
+            foreground,
+            background,
+            underline: style
+                .underline
+                .map(|underline| (underline, style.special.unwrap_or(foreground))),
+            bold: style.bold.unwrap_or_default(),
+            italic: style.italic.unwrap_or_default(),
+            strikethrough: style.strikethrough.unwrap_or_default(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn css(&self) -> String {
+        let mut css = format!(
+            "color: {}; background: {}",
+            CssColor(self.foreground),
+            CssColor(self.background),
+        );
+        if self.bold {
+            css.push_str("; font-weight: bold");
+        }
+        if self.italic {
+            css.push_str("; font-style: italic");
+        }
+        let mut lines = Vec::new();
+        if self.underline.is_some() {
+            lines.push("underline");
+        }
+        if self.strikethrough {
+            lines.push("line-through");
+        }
+        if !lines.is_empty() {
+            css.push_str(&format!("; text-decoration: {}", lines.join(" ")));
+        }
+        if let Some((style, color)) = self.underline {
+            let style = match style {
+                UnderlineStyle::Single => "solid",
+                UnderlineStyle::Double => "double",
+                UnderlineStyle::Curly => "wavy",
+                UnderlineStyle::Dotted => "dotted",
+                UnderlineStyle::Dashed => "dashed",
+            };
+            css.push_str(&format!(" {style} {}", CssColor(color)));
+        }
+        css
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Inverted, SyntarkTheme};
+
+    fn sheet() -> SwatchSheet {
+        SwatchSheet::new(
+            "syntark",
+            &SyntarkTheme::default(),
+            &Inverted::new(SyntarkTheme::default()),
+        )
+    }
+
+    fn assert_well_formed(markup: &str) {
+        let mut open = Vec::new();
+        let mut rest = markup;
+        while let Some(start) = rest.find('<') {
+            assert!(!rest[..start].contains('>'), "stray `>` in {rest:.80}");
+            let end = rest[start..].find('>').unwrap() + start;
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('!') || tag.ends_with('/') || tag.starts_with("meta ") {
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap();
+            match name.strip_prefix('/') {
+                Some(name) => assert_eq!(open.pop(), Some(name), "unbalanced `</{name}>`"),
+                None => open.push(name),
+            }
+        }
+        assert!(open.is_empty(), "unclosed {open:?}");
+    }
+
+    #[test]
+    fn outputs_start_with_marker_and_are_well_formed() {
+        let sheet = sheet();
+        for markup in [sheet.to_html(), sheet.to_svg()] {
+            assert!(markup.lines().next().unwrap().contains(GENERATED_MARKER));
+            assert_well_formed(&markup);
+        }
+    }
+
+    #[test]
+    fn every_color_is_labelled_with_its_value() {
+        let theme = SyntarkTheme::default();
+        let sheet = SwatchSheet::single("syntark", &theme);
+        let (html, svg) = (sheet.to_html(), sheet.to_svg());
+        let entries = enum_iterator::all::<Category>()
+            .map(|c| (format!("{c:?}"), ColorEntry::from(c)))
+            .chain(enum_iterator::all::<Token>().map(|t| (format!("{t:?}"), t.into())))
+            .chain(enum_iterator::all::<DiagnosticLevel>().map(|l| (format!("{l:?}"), l.into())));
+        for (label, entry) in entries {
+            let hex = CssColor(entry.color(&theme)).to_string();
+            assert!(
+                html.contains(&format!("<div>{label}</div><div class=\"details\">{hex} ")),
+                "{label}"
+            );
+            let line = svg
+                .lines()
+                .position(|line| line.ends_with(&format!(">{label}</text>")))
+                .unwrap();
+            assert!(svg
+                .lines()
+                .nth(line + 1)
+                .unwrap()
+                .contains(&format!(">{hex} ")));
+        }
+    }
+
+    #[test]
+    fn contrast_is_measured_against_the_right_background() {
+        let theme = SyntarkTheme::default();
+        let variant = SwatchVariant::new("dark", &theme);
+        let swatch = |label: &str| {
+            variant
+                .groups
+                .iter()
+                .flat_map(|(_, swatches)| swatches)
+                .find(|swatch| swatch.label == label)
+                .unwrap()
+        };
+        let foreground = theme.category_color(Category::Normal);
+        let background = theme.category_color(Category::NormalBackground);
+        let selection = swatch("Selection");
+        assert_eq!(selection.text, Some(foreground));
+        assert_eq!(
+            selection.wcag_ratio,
+            wcag_contrast_ratio(foreground, selection.color)
+        );
+        for label in ["Normal", "Keyword", "Error"] {
+            let swatch = swatch(label);
+            assert_eq!(swatch.text, None);
+            assert_eq!(
+                swatch.wcag_ratio,
+                wcag_contrast_ratio(swatch.color, background)
+            );
+            assert_eq!(swatch.apca_lc, apca_contrast(swatch.color, background));
+        }
+    }
+
+    #[test]
+    fn span_css_combines_decorations() {
+        let (fg, bg, red) = (
+            Srgb::new(0xd8, 0xd8, 0xd8),
+            Srgb::new(0x18, 0x18, 0x18),
+            Srgb::new(0xff, 0, 0),
+        );
+        let css = |style: Style| Span::new("x", style, fg, bg).css();
+        let base = "color: #d8d8d8; background: #181818";
+        assert_eq!(css(Style::default()), base);
+        assert_eq!(
+            css(Style::default().strikethrough()),
+            format!("{base}; text-decoration: line-through")
+        );
+        assert_eq!(
+            css(Style::default().curly_underline().special(red)),
+            format!("{base}; text-decoration: underline wavy #ff0000")
+        );
+        assert_eq!(
+            css(Style::default().dashed_underline().strikethrough()),
+            format!("{base}; text-decoration: underline line-through dashed #d8d8d8")
+        );
+    }
+
+    #[test]
+    fn theme_names_are_escaped() {
+        let sheet = SwatchSheet::single("<b>&", &SyntarkTheme::default());
+        for markup in [sheet.to_html(), sheet.to_svg()] {
+            assert!(markup.contains("&lt;b&gt;&amp;"));
+            assert!(!markup.contains("<b>"));
+            assert_well_formed(&markup);
+        }
+    }
+
+    #[test]
+    fn single_sheets_pick_the_variant_from_the_background() {
+        let dark = SwatchSheet::single("syntark", &SyntarkTheme::default());
+        let light = SwatchSheet::single("syntark", &Inverted::new(SyntarkTheme::default()));
+        assert_eq!(dark.variants.len(), 1);
+        assert_eq!(dark.variants[0].title, "dark");
+        assert_eq!(light.variants[0].title, "light");
+    }
+}
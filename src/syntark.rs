@@ -1,4 +1,7 @@
-use crate::{darken, lighten, Category, DiagnosticLevel, Theme, Token};
+use crate::{
+    adjust_lightness, theme::emphasized_token_style, AnsiColor, Category, DiagnosticLevel, Style,
+    Theme, Token,
+};
 use palette::{FromColor, Hsv, Srgb};
 
 #[derive(Debug, Default)]
@@ -11,8 +14,12 @@ impl Theme for SyntarkTheme {
             Category::ActiveTab => palette::named::WHITE,
             Category::ActiveTabBackground => self.category_color(Category::NormalBackground),
             Category::BadSpelling => palette::named::BROWN,
-            Category::ColumnGuide => darken(self.category_color(Category::NormalBackground), 0.1),
-            Category::CursorLine => lighten(self.category_color(Category::NormalBackground), 0.01),
+            Category::ColumnGuide => {
+                adjust_lightness(self.category_color(Category::NormalBackground), -0.01)
+            }
+            Category::CursorLine => {
+                adjust_lightness(self.category_color(Category::NormalBackground), 0.06)
+            }
             Category::CursorLineNumber => palette::named::WHITE,
             Category::DiffAdd => Srgb::from_color(Hsv {
                 saturation: 0.3,
@@ -33,10 +40,12 @@ impl Theme for SyntarkTheme {
             })
             .into_format(),
             Category::DiffText => self.category_color(Category::DiffAdd),
-            Category::Folded => darken(self.category_color(Category::NormalBackground), 0.2),
-            Category::InactiveTab => darken(self.category_color(Category::Normal), 0.75),
+            Category::Folded => {
+                adjust_lightness(self.category_color(Category::NormalBackground), -0.015)
+            }
+            Category::InactiveTab => adjust_lightness(self.category_color(Category::Normal), -0.33),
             Category::InactiveTabBackground => palette::named::BLACK,
-            Category::LineNumber => darken(self.category_color(Category::Normal), 0.75),
+            Category::LineNumber => adjust_lightness(self.category_color(Category::Normal), -0.33),
             Category::MatchedBracket => palette::named::DEEPPINK,
             Category::MessageSeparator => self.category_color(Category::Normal),
             Category::ModeMessage => self.category_color(Category::Normal),
@@ -48,10 +57,12 @@ impl Theme for SyntarkTheme {
             Category::SearchMatch => palette::named::BLUE,
             Category::Selection => palette::named::DARKSLATEGRAY,
             Category::Special => palette::named::DODGERBLUE,
-            Category::StatusLine => lighten(self.category_color(Category::NormalBackground), 0.005),
+            Category::StatusLine => {
+                adjust_lightness(self.category_color(Category::NormalBackground), 0.035)
+            }
             Category::TermCursor => 0xaeafad.into(),
             Category::UnfocusedTermCursor => self.category_color(Category::TermCursor),
-            Category::Whitespace => darken(self.category_color(Category::Normal), 0.9),
+            Category::Whitespace => adjust_lightness(self.category_color(Category::Normal), -0.47),
         }
     }
 
@@ -90,6 +101,10 @@ impl Theme for SyntarkTheme {
         }
     }
 
+    fn token_style(&self, token: Token) -> Style {
+        emphasized_token_style(self, token)
+    }
+
     fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
         match level {
             DiagnosticLevel::Error => palette::named::CRIMSON,
@@ -98,4 +113,25 @@ impl Theme for SyntarkTheme {
             DiagnosticLevel::Hint => palette::named::AQUA,
         }
     }
+
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        match color {
+            AnsiColor::Black => self.category_color(Category::CursorLine),
+            AnsiColor::Red => palette::named::CRIMSON,
+            AnsiColor::Green => palette::named::SEAGREEN,
+            AnsiColor::Yellow => palette::named::GOLDENROD,
+            AnsiColor::Blue => palette::named::DODGERBLUE,
+            AnsiColor::Magenta => palette::named::ORCHID,
+            AnsiColor::Cyan => palette::named::DARKCYAN,
+            AnsiColor::White => self.category_color(Category::Normal),
+            AnsiColor::BrightBlack => palette::named::DIMGRAY,
+            AnsiColor::BrightRed => palette::named::LIGHTCORAL,
+            AnsiColor::BrightGreen => palette::named::LIGHTGREEN,
+            AnsiColor::BrightYellow => palette::named::KHAKI,
+            AnsiColor::BrightBlue => palette::named::DEEPSKYBLUE,
+            AnsiColor::BrightMagenta => palette::named::PINK,
+            AnsiColor::BrightCyan => palette::named::AQUAMARINE,
+            AnsiColor::BrightWhite => palette::named::WHITE,
+        }
+    }
 }
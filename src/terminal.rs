@@ -0,0 +1,261 @@
+use crate::{color::CssColor, output::GENERATED_MARKER, AnsiColor, Category, Theme};
+use heck::ToSnakeCase;
+use palette::Srgb;
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+struct TerminalColors {
+    foreground: Srgb<u8>,
+    background: Srgb<u8>,
+    cursor: Srgb<u8>,
+    cursor_text: Srgb<u8>,
+    selection_foreground: Srgb<u8>,
+    selection_background: Srgb<u8>,
+    ansi: Vec<(AnsiColor, Srgb<u8>)>,
+}
+
+impl TerminalColors {
+    fn new<T: Theme + ?Sized>(base: &T) -> Self {
+        Self {
+            foreground: base.category_color(Category::Normal),
+            background: base.category_color(Category::NormalBackground),
+            cursor: base.category_color(Category::TermCursor),
+            cursor_text: base.category_color(Category::NormalBackground),
+            selection_foreground: base.category_color(Category::Normal),
+            selection_background: base.category_color(Category::Selection),
+            ansi: enum_iterator::all::<AnsiColor>()
+                .map(|color| (color, base.ansi_color(color)))
+                .collect(),
+        }
+    }
+
+    fn normal(&self) -> impl Iterator<Item = (AnsiColor, Srgb<u8>)> + '_ {
+        self.ansi.iter().copied().filter(|(c, _)| !c.is_bright())
+    }
+
+    fn bright(&self) -> impl Iterator<Item = (AnsiColor, Srgb<u8>)> + '_ {
+        self.ansi.iter().copied().filter(|(c, _)| c.is_bright())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlacrittyTheme {
+    name: String,
+    colors: TerminalColors,
+}
+
+impl AlacrittyTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            colors: TerminalColors::new(base),
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let colors = &self.colors;
+        writeln!(writer, "# {GENERATED_MARKER}")?;
+        writeln!(writer, "# {}", self.name)?;
+        writeln!(writer)?;
+        writeln!(writer, "[colors.primary]")?;
+        writeln!(writer, r#"foreground = "{}""#, CssColor(colors.foreground))?;
+        writeln!(writer, r#"background = "{}""#, CssColor(colors.background))?;
+        writeln!(writer)?;
+        writeln!(writer, "[colors.cursor]")?;
+        writeln!(writer, r#"text = "{}""#, CssColor(colors.cursor_text))?;
+        writeln!(writer, r#"cursor = "{}""#, CssColor(colors.cursor))?;
+        writeln!(writer)?;
+        writeln!(writer, "[colors.selection]")?;
+        writeln!(
+            writer,
+            r#"text = "{}""#,
+            CssColor(colors.selection_foreground)
+        )?;
+        writeln!(
+            writer,
+            r#"background = "{}""#,
+            CssColor(colors.selection_background)
+        )?;
+        let sections: [(&str, Vec<_>); 2] = [
+            ("normal", colors.normal().collect()),
+            ("bright", colors.bright().collect()),
+        ];
+        for (section, ansi) in sections {
+            writeln!(writer)?;
+            writeln!(writer, "[colors.{section}]")?;
+            for (color, value) in ansi {
+                let key = format!("{:?}", color.normal()).to_snake_case();
+                writeln!(writer, r#"{key} = "{}""#, CssColor(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KittyTheme {
+    name: String,
+    colors: TerminalColors,
+}
+
+impl KittyTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            colors: TerminalColors::new(base),
+        }
+    }
+
+    pub fn to_conf(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let colors = &self.colors;
+        writeln!(writer, "# {GENERATED_MARKER}")?;
+        writeln!(writer, "# {}", self.name)?;
+        writeln!(writer)?;
+        let entries = [
+            ("foreground", colors.foreground),
+            ("background", colors.background),
+            ("cursor", colors.cursor),
+            ("cursor_text_color", colors.cursor_text),
+            ("selection_foreground", colors.selection_foreground),
+            ("selection_background", colors.selection_background),
+        ];
+        for (key, value) in entries {
+            writeln!(writer, "{key} {}", CssColor(value))?;
+        }
+        writeln!(writer)?;
+        for &(color, value) in &colors.ansi {
+            writeln!(writer, "color{} {}", color.index(), CssColor(value))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WezTermTheme {
+    name: String,
+    colors: TerminalColors,
+}
+
+impl WezTermTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            colors: TerminalColors::new(base),
+        }
+    }
+
+    pub fn to_lua_module(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        let colors = &self.colors;
+        writeln!(writer, "-- {GENERATED_MARKER}")?;
+        writeln!(writer, "-- {}", self.name)?;
+        writeln!(writer, "return {{")?;
+        let entries = [
+            ("foreground", colors.foreground),
+            ("background", colors.background),
+            ("cursor_bg", colors.cursor),
+            ("cursor_fg", colors.cursor_text),
+            ("cursor_border", colors.cursor),
+            ("selection_fg", colors.selection_foreground),
+            ("selection_bg", colors.selection_background),
+        ];
+        for (key, value) in entries {
+            writeln!(writer, r#"  {key} = "{}","#, CssColor(value))?;
+        }
+        let palettes: [(&str, Vec<_>); 2] = [
+            ("ansi", colors.normal().collect()),
+            ("brights", colors.bright().collect()),
+        ];
+        for (key, ansi) in palettes {
+            let values = ansi
+                .into_iter()
+                .map(|(_, value)| format!(r#""{}""#, CssColor(value)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(writer, "  {key} = {{ {values} }},")?;
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    #[test]
+    fn alacritty_output_is_toml() {
+        let theme = SyntarkTheme::default();
+        let output = AlacrittyTheme::new("syntark", &theme).to_toml();
+        assert!(output.lines().next().unwrap().contains(GENERATED_MARKER));
+        let table: toml::Table = output.parse().unwrap();
+        let colors = table["colors"].as_table().unwrap();
+        assert_eq!(
+            colors["bright"]["magenta"].as_str(),
+            Some(&*CssColor(theme.ansi_color(AnsiColor::BrightMagenta)).to_string())
+        );
+        assert_eq!(colors["normal"].as_table().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn kitty_lists_the_palette_by_index() {
+        let theme = SyntarkTheme::default();
+        let output = KittyTheme::new("syntark", &theme).to_conf();
+        assert!(output.lines().next().unwrap().contains(GENERATED_MARKER));
+        let expected = format!(
+            "color9 {}",
+            CssColor(theme.ansi_color(AnsiColor::BrightRed))
+        );
+        assert!(output.lines().any(|line| line == expected));
+        assert_eq!(
+            output.lines().filter(|l| l.starts_with("color")).count(),
+            16
+        );
+    }
+
+    #[test]
+    fn wezterm_splits_normal_and_bright_colors() {
+        let theme = SyntarkTheme::default();
+        let output = WezTermTheme::new("syntark", &theme).to_lua_module();
+        assert!(output.lines().next().unwrap().contains(GENERATED_MARKER));
+        let ansi = output
+            .lines()
+            .find(|line| line.starts_with("  ansi = "))
+            .unwrap();
+        assert!(ansi.starts_with(&format!(
+            r#"  ansi = {{ "{}", "#,
+            CssColor(theme.ansi_color(AnsiColor::Black))
+        )));
+        assert_eq!(ansi.matches('#').count(), 8);
+    }
+}
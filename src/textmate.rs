@@ -0,0 +1,260 @@
+use crate::{
+    color::CssColor,
+    output::{Escaped, GENERATED_MARKER},
+    Category, DiagnosticLevel, Style, Theme, Token,
+};
+use palette::Srgb;
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TmTheme {
+    name: String,
+    global: Vec<(&'static str, Srgb<u8>)>,
+    rules: Vec<TmRule>,
+}
+
+impl TmTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        let global = [
+            ("background", Category::NormalBackground),
+            ("foreground", Category::Normal),
+            ("caret", Category::TermCursor),
+            ("lineHighlight", Category::CursorLine),
+            ("selection", Category::Selection),
+            ("gutter", Category::NormalBackground),
+            ("gutterForeground", Category::LineNumber),
+            ("findHighlight", Category::SearchMatch),
+            ("findHighlightForeground", Category::Search),
+            ("invisibles", Category::Whitespace),
+            ("guide", Category::ColumnGuide),
+            ("bracketsForeground", Category::MatchedBracket),
+        ]
+        .into_iter()
+        .map(|(key, category)| (key, base.category_color(category)))
+        .collect();
+        let normal = base.category_color(Category::Normal);
+        let diff_rule = |name: &str, scope: &str, category| TmRule {
+            name: name.into(),
+            scope: scope.into(),
+            style: Style::default()
+                .foreground(normal)
+                .background(base.category_color(category)),
+        };
+        let diagnostic_rule = |name: &str, scope: &str, level| TmRule {
+            name: name.into(),
+            scope: scope.into(),
+            style: Style::default().foreground(base.diagnostic_level_color(level)),
+        };
+        let rules = enum_iterator::all::<Token>()
+            .filter(|&token| !token_scopes(token).is_empty())
+            .map(|token| TmRule {
+                name: format!("{token:?}"),
+                scope: token_scopes(token).join(", "),
+                style: base.token_style(token),
+            })
+            .chain([
+                diff_rule("DiffAdd", "markup.inserted", Category::DiffAdd),
+                diff_rule("DiffDelete", "markup.deleted", Category::DiffDelete),
+                diff_rule("DiffChange", "markup.changed", Category::DiffChange),
+                diagnostic_rule("Error", "invalid", DiagnosticLevel::Error),
+                diagnostic_rule("Deprecated", "invalid.deprecated", DiagnosticLevel::Warning),
+            ])
+            .collect();
+        Self {
+            name: name.into(),
+            global,
+            rules,
+        }
+    }
+
+    pub fn to_plist(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(
+            writer,
+            r#"<?xml version="1.0" encoding="UTF-8"?><!-- {GENERATED_MARKER} -->"#
+        )?;
+        writeln!(
+            writer,
+            r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#
+        )?;
+        writeln!(writer, r#"<plist version="1.0">"#)?;
+        writeln!(writer, "<dict>")?;
+        write_entry(&mut writer, 1, "name", &self.name)?;
+        writeln!(writer, "  <key>settings</key>")?;
+        writeln!(writer, "  <array>")?;
+        writeln!(writer, "    <dict>")?;
+        writeln!(writer, "      <key>settings</key>")?;
+        writeln!(writer, "      <dict>")?;
+        for &(key, color) in &self.global {
+            write_entry(&mut writer, 4, key, &CssColor(color).to_string())?;
+        }
+        writeln!(writer, "      </dict>")?;
+        writeln!(writer, "    </dict>")?;
+        for rule in &self.rules {
+            writeln!(writer, "    <dict>")?;
+            write_entry(&mut writer, 3, "name", &rule.name)?;
+            write_entry(&mut writer, 3, "scope", &rule.scope)?;
+            writeln!(writer, "      <key>settings</key>")?;
+            writeln!(writer, "      <dict>")?;
+            if let Some(color) = rule.style.foreground {
+                write_entry(&mut writer, 4, "foreground", &CssColor(color).to_string())?;
+            }
+            if let Some(color) = rule.style.background {
+                write_entry(&mut writer, 4, "background", &CssColor(color).to_string())?;
+            }
+            if let Some(font_style) = font_style(&rule.style) {
+                write_entry(&mut writer, 4, "fontStyle", &font_style)?;
+            }
+            writeln!(writer, "      </dict>")?;
+            writeln!(writer, "    </dict>")?;
+        }
+        writeln!(writer, "  </array>")?;
+        writeln!(writer, "</dict>")?;
+        writeln!(writer, "</plist>")?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct TmRule {
+    name: String,
+    scope: String,
+    style: Style,
+}
+
+fn write_entry<W>(writer: &mut W, depth: usize, key: &str, value: &str) -> io::Result<()>
+where
+    W: Write,
+{
+    let indent = "  ".repeat(depth);
+    writeln!(writer, "{indent}<key>{key}</key>")?;
+    writeln!(writer, "{indent}<string>{}</string>", Escaped(value))
+}
+
+pub(crate) fn font_style(style: &Style) -> Option<String> {
+    let flags = [
+        (style.bold, "bold"),
+        (style.italic, "italic"),
+        (style.underline.map(|_| true), "underline"),
+        (style.strikethrough, "strikethrough"),
+    ];
+    if flags.iter().all(|(flag, _)| flag.is_none()) {
+        return None;
+    }
+    let font_style = flags
+        .into_iter()
+        .filter_map(|(flag, name)| flag.unwrap_or(false).then_some(name))
+        .collect::<Vec<_>>();
+    Some(font_style.join(" "))
+}
+
+pub(crate) fn token_scopes(token: Token) -> &'static [&'static str] {
+    match token {
+        Token::Attribute => &[
+            "meta.attribute",
+            "meta.annotation",
+            "storage.type.annotation",
+        ],
+        Token::Boolean => &["constant.language.boolean"],
+        Token::Character => &["constant.character", "string.quoted.single.char"],
+        Token::Comment => &["comment", "punctuation.definition.comment"],
+        Token::Constant => &["constant.other", "variable.other.constant"],
+        Token::ConstGenericParameter => &[],
+        Token::Delimiter => &["punctuation", "meta.brace"],
+        Token::DocComment => &["comment.block.documentation", "comment.line.documentation"],
+        Token::Enum => &["entity.name.type.enum"],
+        Token::Field => &["variable.other.property", "variable.other.member"],
+        Token::Float => &["constant.numeric.float"],
+        Token::Function => &["entity.name.function", "support.function"],
+        Token::Identifier => &[],
+        Token::Integer => &["constant.numeric"],
+        Token::Interface => &["entity.name.type.interface", "entity.name.type.trait"],
+        Token::Keyword => &["keyword", "storage.type", "storage.modifier"],
+        Token::Link => &["markup.underline.link"],
+        Token::Macro => &["entity.name.function.macro", "support.function.macro"],
+        Token::Module => &["entity.name.namespace", "entity.name.module"],
+        Token::Operator => &["keyword.operator"],
+        Token::Parameter => &["variable.parameter"],
+        Token::Static => &[],
+        Token::String => &["string"],
+        Token::Struct => &["entity.name.type.struct"],
+        Token::Tag => &["entity.name.tag"],
+        Token::Todo => &[],
+        Token::Type => &[
+            "entity.name.type",
+            "entity.name.class",
+            "support.type",
+            "support.class",
+        ],
+        Token::TypeParameter => &["entity.name.type.parameter"],
+        Token::Variable => &["variable", "variable.other"],
+        Token::Variant => &["variable.other.enummember"],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    #[test]
+    fn marker_follows_the_xml_declaration() {
+        let output = TmTheme::new("syntark", &SyntarkTheme::default()).to_plist();
+        assert_eq!(
+            output.lines().next(),
+            Some(r#"<?xml version="1.0" encoding="UTF-8"?><!-- Generated by syntinct -->"#)
+        );
+        assert!(output.ends_with("</plist>\n"));
+    }
+
+    #[test]
+    fn values_are_escaped() {
+        let output = TmTheme::new("a<b>&\"c\"", &SyntarkTheme::default()).to_plist();
+        assert!(output.contains("  <string>a&lt;b&gt;&amp;&quot;c&quot;</string>\n"));
+    }
+
+    #[test]
+    fn rules_carry_token_styles() {
+        let base = SyntarkTheme::default();
+        let output = TmTheme::new("syntark", &base).to_plist();
+        let expected = format!(
+            "      <key>name</key>
+      <string>Keyword</string>
+      <key>scope</key>
+      <string>keyword, storage.type, storage.modifier</string>
+      <key>settings</key>
+      <dict>
+        <key>foreground</key>
+        <string>{}</string>
+        <key>fontStyle</key>
+        <string>bold</string>
+      </dict>
+",
+            CssColor(base.token_color(Token::Keyword))
+        );
+        assert!(output.contains(&expected), "{output}");
+    }
+
+    #[test]
+    fn font_style_is_only_set_when_given() {
+        assert_eq!(font_style(&Style::default()), None);
+        assert_eq!(
+            font_style(&Style::default().italic().curly_underline()),
+            Some("italic underline".into())
+        );
+        let unset = Style {
+            bold: Some(false),
+            ..Style::default()
+        };
+        assert_eq!(font_style(&unset), Some("".into()));
+    }
+}
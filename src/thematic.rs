@@ -1,96 +1,71 @@
-use crate::{Category, DiagnosticLevel, Theme, Token};
+use crate::{
+    color::deserialize_color,
+    theme::{brighten, emphasized_token_style},
+    to_oklch, AnsiColor, Category, DiagnosticLevel, Style, Theme, Token,
+};
 use palette::Srgb;
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-enum ThematicVariant {
-    Dark,
-    Light,
-}
+use serde::Deserialize;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug)]
 pub struct ThematicTheme {
-    variant: ThematicVariant,
+    provider: Box<dyn Provider + Send + Sync>,
 }
 
 impl ThematicTheme {
     pub fn dark() -> Self {
-        Self {
-            variant: ThematicVariant::Dark,
-        }
+        Self::from_provider(DarkThematicTheme)
     }
 
     pub fn light() -> Self {
+        Self::from_provider(LightThematicTheme)
+    }
+
+    /// Theme mapping the semantic colors of `provider` to every category and token.
+    pub fn from_provider<P>(provider: P) -> Self
+    where
+        P: Provider + Send + Sync + 'static,
+    {
         Self {
-            variant: ThematicVariant::Light,
+            provider: Box::new(provider),
         }
     }
 }
 
 impl Theme for ThematicTheme {
     fn category_color(&self, category: Category) -> Srgb<u8> {
-        match self.variant {
-            ThematicVariant::Dark => DarkThematicTheme.category_color(category),
-            ThematicVariant::Light => LightThematicTheme.category_color(category),
-        }
+        category_color(&*self.provider, category)
     }
 
     fn token_color(&self, token: Token) -> Srgb<u8> {
-        match self.variant {
-            ThematicVariant::Dark => DarkThematicTheme.token_color(token),
-            ThematicVariant::Light => LightThematicTheme.token_color(token),
-        }
+        token_color(&*self.provider, token)
     }
 
-    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
-        match self.variant {
-            ThematicVariant::Dark => DarkThematicTheme.diagnostic_level_color(level),
-            ThematicVariant::Light => LightThematicTheme.diagnostic_level_color(level),
-        }
-    }
-}
-
-#[derive(Debug)]
-struct DarkThematicTheme;
-
-impl Theme for DarkThematicTheme {
-    fn category_color(&self, category: Category) -> Srgb<u8> {
-        category_color(self, category)
+    fn token_style(&self, token: Token) -> Style {
+        emphasized_token_style(self, token)
     }
 
-    fn token_color(&self, token: Token) -> Srgb<u8> {
-        token_color(self, token)
+    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
+        self.provider.diagnostic_level_color(level)
     }
 
-    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
-        match level {
-            DiagnosticLevel::Error => palette::named::CRIMSON,
-            DiagnosticLevel::Warning => palette::named::ORANGE,
-            DiagnosticLevel::Info => palette::named::STEELBLUE,
-            DiagnosticLevel::Hint => palette::named::AQUA,
-        }
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        self.provider.ansi_color(color)
     }
 }
 
 #[derive(Debug)]
-struct LightThematicTheme;
-
-impl Theme for LightThematicTheme {
-    fn category_color(&self, category: Category) -> Srgb<u8> {
-        category_color(self, category)
-    }
-
-    fn token_color(&self, token: Token) -> Srgb<u8> {
-        token_color(self, token)
-    }
+struct DarkThematicTheme;
 
-    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
-        DarkThematicTheme.diagnostic_level_color(level)
-    }
-}
+#[derive(Debug)]
+struct LightThematicTheme;
 
 fn category_color<P>(provider: &P, category: Category) -> Srgb<u8>
 where
-    P: Provider,
+    P: Provider + ?Sized,
 {
     match category {
         Category::ActiveSearchMatch => provider.active_search_match(),
@@ -128,7 +103,7 @@ where
 
 fn token_color<P>(provider: &P, token: Token) -> Srgb<u8>
 where
-    P: Provider,
+    P: Provider + ?Sized,
 {
     match token {
         Token::Attribute => palette::named::LIGHTPINK,
@@ -164,7 +139,32 @@ where
     }
 }
 
-trait Provider {
+// Greys are picked among the provider colors depending on whether the background is dark.
+fn ansi_color<P>(provider: &P, color: AnsiColor) -> Srgb<u8>
+where
+    P: Provider + ?Sized,
+{
+    let dark = to_oklch(provider.background()).l <= 0.5;
+    match color {
+        AnsiColor::Black if dark => provider.selection(),
+        AnsiColor::Black => provider.foreground(),
+        AnsiColor::White if dark => provider.foreground(),
+        AnsiColor::White => provider.secondary_foreground(),
+        AnsiColor::BrightBlack if dark => provider.secondary_foreground(),
+        AnsiColor::BrightBlack => provider.cursor(),
+        AnsiColor::BrightWhite if !dark => provider.secondary_background(),
+        AnsiColor::Red => provider.error(),
+        AnsiColor::Green => provider.diff_add(),
+        AnsiColor::Yellow => 0xebcb8b.into(),
+        AnsiColor::Blue => 0x81a1c1.into(),
+        AnsiColor::Magenta => 0xb48ead.into(),
+        AnsiColor::Cyan => 0x88c0d0.into(),
+        _ => brighten(provider.background(), provider.ansi_color(color.normal())),
+    }
+}
+
+/// Semantic colors from which [`ThematicTheme`] derives every category and token color.
+pub trait Provider: fmt::Debug {
     fn active_search_match(&self) -> Srgb<u8>;
     fn error(&self) -> Srgb<u8>;
     fn diff_add(&self) -> Srgb<u8>;
@@ -179,6 +179,155 @@ trait Provider {
     fn selection(&self) -> Srgb<u8>;
     fn matched_bracket(&self) -> Srgb<u8>;
     fn cursor(&self) -> Srgb<u8>;
+
+    fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8> {
+        match level {
+            DiagnosticLevel::Error => palette::named::CRIMSON,
+            DiagnosticLevel::Warning => palette::named::ORANGE,
+            DiagnosticLevel::Info => palette::named::STEELBLUE,
+            DiagnosticLevel::Hint => palette::named::AQUA,
+        }
+    }
+
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        ansi_color(self, color)
+    }
+}
+
+/// Colors of a [`Provider`], deserializable from color names or `#rrggbb` values.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ThematicPalette {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub active_search_match: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub error: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub diff_add: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub diff_change: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub diff_delete: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub guide: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub foreground: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub background: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub secondary_background: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub secondary_foreground: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub search_match: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub selection: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub matched_bracket: Srgb<u8>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub cursor: Srgb<u8>,
+}
+
+impl ThematicPalette {
+    pub fn from_path<P>(path: P) -> Result<Self, ThematicPaletteError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let read = || {
+            fs::read_to_string(path).map_err(|source| ThematicPaletteError::Io {
+                path: path.into(),
+                source,
+            })
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&read()?),
+            Some("json") => Self::from_json_str(&read()?),
+            _ => Err(ThematicPaletteError::UnknownFormat(path.into())),
+        }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, ThematicPaletteError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, ThematicPaletteError> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThematicPaletteError {
+    #[error("Failed to read palette file {path}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Unknown palette file format for {0}; expected a `.toml` or `.json` extension")]
+    UnknownFormat(PathBuf),
+    #[error("Failed to parse TOML palette")]
+    Toml(#[from] toml::de::Error),
+    #[error("Failed to parse JSON palette")]
+    Json(#[from] serde_json::Error),
+}
+
+impl Provider for ThematicPalette {
+    fn active_search_match(&self) -> Srgb<u8> {
+        self.active_search_match
+    }
+
+    fn error(&self) -> Srgb<u8> {
+        self.error
+    }
+
+    fn diff_add(&self) -> Srgb<u8> {
+        self.diff_add
+    }
+
+    fn diff_change(&self) -> Srgb<u8> {
+        self.diff_change
+    }
+
+    fn diff_delete(&self) -> Srgb<u8> {
+        self.diff_delete
+    }
+
+    fn guide(&self) -> Srgb<u8> {
+        self.guide
+    }
+
+    fn foreground(&self) -> Srgb<u8> {
+        self.foreground
+    }
+
+    fn background(&self) -> Srgb<u8> {
+        self.background
+    }
+
+    fn secondary_background(&self) -> Srgb<u8> {
+        self.secondary_background
+    }
+
+    fn secondary_foreground(&self) -> Srgb<u8> {
+        self.secondary_foreground
+    }
+
+    fn search_match(&self) -> Srgb<u8> {
+        self.search_match
+    }
+
+    fn selection(&self) -> Srgb<u8> {
+        self.selection
+    }
+
+    fn matched_bracket(&self) -> Srgb<u8> {
+        self.matched_bracket
+    }
+
+    fn cursor(&self) -> Srgb<u8> {
+        self.cursor
+    }
 }
 
 impl Provider for DarkThematicTheme {
@@ -237,6 +386,13 @@ impl Provider for DarkThematicTheme {
     fn cursor(&self) -> Srgb<u8> {
         0xd8dee9.into()
     }
+
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        match color {
+            AnsiColor::BrightWhite => 0xeceff4.into(),
+            _ => ansi_color(self, color),
+        }
+    }
 }
 
 impl Provider for LightThematicTheme {
@@ -296,3 +452,65 @@ impl Provider for LightThematicTheme {
         palette::named::SLATEGRAY
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PALETTE: &str = r##"
+        active_search_match = "coral"
+        error = "#bf616a"
+        diff_add = "#a3be8c"
+        diff_change = "#202020"
+        diff_delete = "#bf616a"
+        guide = "#202020"
+        foreground = "#d8d8d8"
+        background = "#181818"
+        secondary_background = "#202020"
+        secondary_foreground = "slategray"
+        search_match = "royalblue"
+        selection = "#282828"
+        matched_bracket = "royalblue"
+        cursor = "#d8dee9"
+    "##;
+
+    #[test]
+    fn palette_matches_the_dark_theme() {
+        let palette = ThematicPalette::from_toml_str(PALETTE).unwrap();
+        let (theme, dark) = (ThematicTheme::from_provider(palette), ThematicTheme::dark());
+        for category in enum_iterator::all::<Category>() {
+            assert_eq!(
+                theme.category_color(category),
+                dark.category_color(category),
+                "{category:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn json_palettes_are_parsed() {
+        let table: toml::Table = PALETTE.parse().unwrap();
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(
+            ThematicPalette::from_json_str(&json).unwrap(),
+            ThematicPalette::from_toml_str(PALETTE).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_palettes_are_rejected() {
+        let missing = PALETTE.replace("cursor = \"#d8dee9\"", "");
+        let unknown = format!("{PALETTE}\nextra = \"red\"");
+        let invalid = PALETTE.replace("coral", "not-a-color");
+        for s in [missing, unknown, invalid] {
+            assert!(matches!(
+                ThematicPalette::from_toml_str(&s),
+                Err(ThematicPaletteError::Toml(_))
+            ));
+        }
+        assert!(matches!(
+            ThematicPalette::from_path("palette.yaml"),
+            Err(ThematicPaletteError::UnknownFormat(_))
+        ));
+    }
+}
@@ -1,8 +1,48 @@
-use crate::{Category, DiagnosticLevel, Token};
+use crate::{adjust_lightness, to_oklch, AnsiColor, Category, DiagnosticLevel, Style, Token};
 use palette::Srgb;
 
 pub trait Theme {
     fn category_color(&self, category: Category) -> Srgb<u8>;
     fn token_color(&self, token: Token) -> Srgb<u8>;
     fn diagnostic_level_color(&self, level: DiagnosticLevel) -> Srgb<u8>;
+
+    fn token_style(&self, token: Token) -> Style {
+        Style::default().foreground(self.token_color(token))
+    }
+
+    fn ansi_color(&self, color: AnsiColor) -> Srgb<u8> {
+        match color {
+            AnsiColor::Black => self.category_color(Category::CursorLine),
+            AnsiColor::Red => self.diagnostic_level_color(DiagnosticLevel::Error),
+            AnsiColor::Green => self.token_color(Token::String),
+            AnsiColor::Yellow => self.diagnostic_level_color(DiagnosticLevel::Warning),
+            AnsiColor::Blue => self.token_color(Token::Function),
+            AnsiColor::Magenta => self.token_color(Token::Keyword),
+            AnsiColor::Cyan => self.diagnostic_level_color(DiagnosticLevel::Hint),
+            AnsiColor::White => self.category_color(Category::Normal),
+            AnsiColor::BrightBlack => self.category_color(Category::LineNumber),
+            _ => brighten(
+                self.category_color(Category::NormalBackground),
+                self.ansi_color(color.normal()),
+            ),
+        }
+    }
+}
+
+pub(crate) fn emphasized_token_style<T>(theme: &T, token: Token) -> Style
+where
+    T: Theme + ?Sized,
+{
+    let style = Style::default().foreground(theme.token_color(token));
+    match token {
+        Token::Keyword | Token::Todo => style.bold(),
+        Token::Comment | Token::DocComment => style.italic(),
+        _ => style,
+    }
+}
+
+pub(crate) fn brighten(background: Srgb<u8>, color: Srgb<u8>) -> Srgb<u8> {
+    let background = to_oklch(background);
+    let delta = if background.l > 0.5 { -0.08 } else { 0.08 };
+    adjust_lightness(color, delta)
 }
@@ -0,0 +1,187 @@
+use crate::{
+    color::CssColor,
+    neovim::{classic_highlights, terminal_colors, ClassicHighlight},
+    output::GENERATED_MARKER,
+    quantize::CtermPalette,
+    Style, Theme, UnderlineStyle,
+};
+use palette::Srgb;
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VimTheme {
+    name: String,
+    dark_highlights: Vec<(String, ClassicHighlight)>,
+    light_highlights: Vec<(String, ClassicHighlight)>,
+    dark_terminal_colors: Vec<Srgb<u8>>,
+    light_terminal_colors: Vec<Srgb<u8>>,
+    cterm_palette: CtermPalette,
+}
+
+impl VimTheme {
+    pub fn new<S, D, L>(name: S, dark_base: &D, light_base: &L) -> Self
+    where
+        S: Into<String>,
+        D: Theme + ?Sized,
+        L: Theme + ?Sized,
+    {
+        Self {
+            name: name.into(),
+            dark_highlights: classic_highlights(dark_base),
+            light_highlights: classic_highlights(light_base),
+            dark_terminal_colors: terminal_colors(dark_base),
+            light_terminal_colors: terminal_colors(light_base),
+            cterm_palette: CtermPalette::default(),
+        }
+    }
+
+    pub fn cterm_palette(self, palette: CtermPalette) -> Self {
+        Self {
+            cterm_palette: palette,
+            ..self
+        }
+    }
+
+    pub fn to_vim_script(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "\" {GENERATED_MARKER}")?;
+        writeln!(writer, "highlight clear")?;
+        writeln!(writer, "if exists(\"syntax_on\")")?;
+        writeln!(writer, "  syntax reset")?;
+        writeln!(writer, "endif")?;
+        writeln!(writer, "let g:colors_name = \"{}\"", self.name)?;
+        writeln!(writer)?;
+        writeln!(writer, "if &background ==# \"light\"")?;
+        write_highlights(&mut writer, &self.light_highlights, |c| {
+            self.cterm_palette.quantize(c, &self.light_terminal_colors)
+        })?;
+        write_terminal_colors(&mut writer, &self.light_terminal_colors)?;
+        writeln!(writer, "else")?;
+        write_highlights(&mut writer, &self.dark_highlights, |c| {
+            self.cterm_palette.quantize(c, &self.dark_terminal_colors)
+        })?;
+        write_terminal_colors(&mut writer, &self.dark_terminal_colors)?;
+        writeln!(writer, "endif")?;
+        Ok(())
+    }
+}
+
+fn write_highlights<W>(
+    writer: &mut W,
+    highlights: &[(String, ClassicHighlight)],
+    cterm: impl Fn(Srgb<u8>) -> u8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    for (name, highlight) in highlights {
+        match highlight {
+            ClassicHighlight::Value(style) => {
+                let gui = |c: Option<Srgb<u8>>| match c {
+                    Some(c) => CssColor(c).to_string(),
+                    None => "NONE".into(),
+                };
+                let cterm = |c: Option<Srgb<u8>>| match c {
+                    Some(c) => cterm(c).to_string(),
+                    None => "NONE".into(),
+                };
+                writeln!(
+                    writer,
+                    "  highlight {name} guifg={} guibg={} guisp={} gui={} ctermfg={} ctermbg={} \
+                     cterm={}",
+                    gui(style.foreground),
+                    gui(style.background),
+                    gui(style.special),
+                    attributes(style),
+                    cterm(style.foreground),
+                    cterm(style.background),
+                    attributes(style),
+                )?;
+            }
+            ClassicHighlight::Link(link) => writeln!(writer, "  highlight! link {name} {link}")?,
+        }
+    }
+    Ok(())
+}
+
+// Vim 8 only knows `underline` and `undercurl`, so other underline styles fall back to a single
+// underline.
+fn attributes(style: &Style) -> String {
+    let underline = style.underline.map(|underline| match underline {
+        UnderlineStyle::Curly => "undercurl",
+        UnderlineStyle::Single
+        | UnderlineStyle::Double
+        | UnderlineStyle::Dotted
+        | UnderlineStyle::Dashed => "underline",
+    });
+    let attributes = [
+        style.bold.unwrap_or(false).then_some("bold"),
+        style.italic.unwrap_or(false).then_some("italic"),
+        underline,
+        style
+            .strikethrough
+            .unwrap_or(false)
+            .then_some("strikethrough"),
+        style.reversed.then_some("reverse"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if attributes.is_empty() {
+        "NONE".into()
+    } else {
+        attributes.join(",")
+    }
+}
+
+fn write_terminal_colors<W>(writer: &mut W, colors: &[Srgb<u8>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let colors = colors
+        .iter()
+        .map(|&c| format!("\"{}\"", CssColor(c)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(writer, "  let g:terminal_ansi_colors = [{colors}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SyntarkTheme, Token};
+
+    #[test]
+    fn highlights_use_gui_and_cterm_attributes() {
+        let base = SyntarkTheme::default();
+        let output = VimTheme::new("syntark", &base, &base).to_vim_script();
+        assert!(output.lines().next().unwrap().contains(GENERATED_MARKER));
+        assert!(output.contains("let g:colors_name = \"syntark\"\n"));
+        let keyword = output
+            .lines()
+            .find(|line| line.starts_with("  highlight Keyword "))
+            .unwrap();
+        assert!(keyword.starts_with(&format!(
+            "  highlight Keyword guifg={} guibg=NONE guisp=NONE gui=bold ctermfg=",
+            CssColor(base.token_color(Token::Keyword))
+        )));
+        assert!(keyword.ends_with(" ctermbg=NONE cterm=bold"));
+        assert_eq!(output.matches("let g:terminal_ansi_colors = [").count(), 2);
+    }
+
+    #[test]
+    fn underlines_fall_back_to_vim_attributes() {
+        let style = Style::default().curly_underline().bold();
+        assert_eq!(attributes(&style), "bold,undercurl");
+        assert_eq!(
+            attributes(&Style::default().dashed_underline()),
+            "underline"
+        );
+        assert_eq!(attributes(&Style::default()), "NONE");
+    }
+}
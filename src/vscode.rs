@@ -0,0 +1,386 @@
+use crate::{
+    color::CssColor,
+    lsp::LspType,
+    output::{is_generated, write_generated_file, GENERATED_MARKER},
+    textmate::{font_style, token_scopes},
+    to_oklch, AnsiColor, Category, DiagnosticLevel, OutputError, Style, Theme, Token,
+};
+use heck::ToKebabCase;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VsCodeTheme {
+    name: String,
+    #[serde(rename = "type")]
+    kind: VsCodeThemeKind,
+    semantic_highlighting: bool,
+    colors: BTreeMap<&'static str, String>,
+    token_colors: Vec<TokenColor>,
+    semantic_token_colors: BTreeMap<String, SemanticTokenStyle>,
+}
+
+impl VsCodeTheme {
+    pub fn new<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        let kind = if to_oklch(base.category_color(Category::NormalBackground)).l > 0.5 {
+            VsCodeThemeKind::Light
+        } else {
+            VsCodeThemeKind::Dark
+        };
+        let token_colors = enum_iterator::all::<Token>()
+            .filter(|&token| !token_scopes(token).is_empty())
+            .map(|token| TokenColor::new(token, &base.token_style(token)))
+            .collect();
+        let semantic_token_colors = enum_iterator::all::<LspType>()
+            .map(|lsp_type| {
+                let style = base.token_style(lsp_type.token());
+                (lsp_type.to_string(), SemanticTokenStyle::from(&style))
+            })
+            .chain([(
+                "*.deprecated".into(),
+                SemanticTokenStyle {
+                    strikethrough: Some(true),
+                    ..SemanticTokenStyle::default()
+                },
+            )])
+            .collect();
+        Self {
+            name: name.into(),
+            kind,
+            semantic_highlighting: true,
+            colors: workbench_colors(base),
+            token_colors,
+            semantic_token_colors,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    // VS Code reads themes as JSON with comments, which leaves room for the marker.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "// {GENERATED_MARKER}")?;
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum VsCodeThemeKind {
+    Dark,
+    Light,
+}
+
+impl VsCodeThemeKind {
+    fn ui_theme(self) -> &'static str {
+        match self {
+            Self::Dark => "vs-dark",
+            Self::Light => "vs",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct TokenColor {
+    name: String,
+    scope: Vec<&'static str>,
+    settings: TokenSettings,
+}
+
+impl TokenColor {
+    fn new(token: Token, style: &Style) -> Self {
+        Self {
+            name: format!("{token:?}"),
+            scope: token_scopes(token).to_vec(),
+            settings: TokenSettings {
+                foreground: style.foreground.map(|c| CssColor(c).to_string()),
+                background: style.background.map(|c| CssColor(c).to_string()),
+                font_style: font_style(style),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_style: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct SemanticTokenStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strikethrough: Option<bool>,
+}
+
+impl From<&Style> for SemanticTokenStyle {
+    fn from(style: &Style) -> Self {
+        Self {
+            foreground: style.foreground.map(|c| CssColor(c).to_string()),
+            bold: style.bold,
+            italic: style.italic,
+            underline: style.underline.map(|_| true),
+            strikethrough: style.strikethrough,
+        }
+    }
+}
+
+fn workbench_colors<T: Theme + ?Sized>(base: &T) -> BTreeMap<&'static str, String> {
+    let categories = [
+        ("diffEditor.insertedLineBackground", Category::DiffAdd),
+        ("diffEditor.insertedTextBackground", Category::DiffText),
+        ("diffEditor.removedLineBackground", Category::DiffDelete),
+        ("diffEditor.removedTextBackground", Category::DiffDelete),
+        ("editor.background", Category::NormalBackground),
+        ("editor.findMatchBackground", Category::ActiveSearchMatch),
+        ("editor.findMatchHighlightBackground", Category::SearchMatch),
+        ("editor.foldBackground", Category::Folded),
+        ("editor.foreground", Category::Normal),
+        ("editor.lineHighlightBackground", Category::CursorLine),
+        ("editor.selectionBackground", Category::Selection),
+        ("editorBracketMatch.border", Category::MatchedBracket),
+        ("editorCursor.foreground", Category::TermCursor),
+        (
+            "editorGroupHeader.tabsBackground",
+            Category::InactiveTabBackground,
+        ),
+        ("editorGutter.background", Category::NormalBackground),
+        (
+            "editorLineNumber.activeForeground",
+            Category::CursorLineNumber,
+        ),
+        ("editorLineNumber.foreground", Category::LineNumber),
+        ("editorRuler.foreground", Category::ColumnGuide),
+        ("editorWhitespace.foreground", Category::Whitespace),
+        ("statusBar.background", Category::StatusLine),
+        ("statusBar.foreground", Category::Normal),
+        ("tab.activeBackground", Category::ActiveTabBackground),
+        ("tab.activeForeground", Category::ActiveTab),
+        ("tab.inactiveBackground", Category::InactiveTabBackground),
+        ("tab.inactiveForeground", Category::InactiveTab),
+        ("terminal.background", Category::NormalBackground),
+        ("terminal.foreground", Category::Normal),
+        ("terminalCursor.foreground", Category::TermCursor),
+    ]
+    .map(|(key, category)| (key, base.category_color(category)));
+    let diagnostics = [
+        ("editorError.foreground", DiagnosticLevel::Error),
+        ("editorWarning.foreground", DiagnosticLevel::Warning),
+        ("editorInfo.foreground", DiagnosticLevel::Info),
+        ("editorHint.foreground", DiagnosticLevel::Hint),
+    ]
+    .map(|(key, level)| (key, base.diagnostic_level_color(level)));
+    let ansi = enum_iterator::all::<AnsiColor>().map(|color| {
+        let key = match color {
+            AnsiColor::Black => "terminal.ansiBlack",
+            AnsiColor::Red => "terminal.ansiRed",
+            AnsiColor::Green => "terminal.ansiGreen",
+            AnsiColor::Yellow => "terminal.ansiYellow",
+            AnsiColor::Blue => "terminal.ansiBlue",
+            AnsiColor::Magenta => "terminal.ansiMagenta",
+            AnsiColor::Cyan => "terminal.ansiCyan",
+            AnsiColor::White => "terminal.ansiWhite",
+            AnsiColor::BrightBlack => "terminal.ansiBrightBlack",
+            AnsiColor::BrightRed => "terminal.ansiBrightRed",
+            AnsiColor::BrightGreen => "terminal.ansiBrightGreen",
+            AnsiColor::BrightYellow => "terminal.ansiBrightYellow",
+            AnsiColor::BrightBlue => "terminal.ansiBrightBlue",
+            AnsiColor::BrightMagenta => "terminal.ansiBrightMagenta",
+            AnsiColor::BrightCyan => "terminal.ansiBrightCyan",
+            AnsiColor::BrightWhite => "terminal.ansiBrightWhite",
+        };
+        (key, base.ansi_color(color))
+    });
+    categories
+        .into_iter()
+        .chain(diagnostics)
+        .chain(ansi)
+        .map(|(key, color)| (key, CssColor(color).to_string()))
+        .collect()
+}
+
+/// Writes a minimal VS Code extension contributing the given themes to `dir`.
+pub fn write_vscode_extension(
+    dir: &Path,
+    name: &str,
+    themes: &[VsCodeTheme],
+    force: bool,
+) -> Result<Vec<PathBuf>, OutputError> {
+    let files = themes
+        .iter()
+        .map(|theme| {
+            let file_name = format!("{}-color-theme.json", slug(&theme.name));
+            (theme, dir.join("themes").join(&file_name), file_name)
+        })
+        .collect::<Vec<_>>();
+    let package_path = dir.join("package.json");
+    if !force {
+        for (_, path, _) in &files {
+            if path.exists() && !is_generated(path)? {
+                return Err(OutputError::NotGenerated(path.clone()));
+            }
+        }
+        if package_path.exists() && !is_generated_manifest(&package_path)? {
+            return Err(OutputError::NotGenerated(package_path));
+        }
+    }
+    let mut paths = Vec::new();
+    let mut contributions = Vec::new();
+    for (theme, path, file_name) in files {
+        write_generated_file(&path, theme.to_json().as_bytes(), true)?;
+        paths.push(path);
+        contributions.push(ThemeContribution {
+            label: theme.name.clone(),
+            ui_theme: theme.kind.ui_theme(),
+            path: format!("./themes/{file_name}"),
+        });
+    }
+    let manifest = Manifest {
+        name: format!("{}-theme", slug(name)),
+        display_name: name.into(),
+        description: GENERATED_MARKER,
+        version: env!("CARGO_PKG_VERSION"),
+        publisher: "syntinct",
+        engines: Engines { vscode: "^1.70.0" },
+        categories: &["Themes"],
+        contributes: Contributes {
+            themes: contributions,
+        },
+    };
+    let contents = serde_json::to_string_pretty(&manifest).unwrap() + "\n";
+    write_generated_file(&package_path, contents.as_bytes(), true)?;
+    paths.push(package_path);
+    Ok(paths)
+}
+
+fn is_generated_manifest(path: &Path) -> Result<bool, OutputError> {
+    let contents = fs::read_to_string(path).map_err(|source| OutputError::Io {
+        path: path.into(),
+        source,
+    })?;
+    let description = serde_json::from_str::<serde_json::Value>(&contents)
+        .ok()
+        .and_then(|manifest| manifest["description"].as_str().map(String::from));
+    Ok(description.as_deref() == Some(GENERATED_MARKER))
+}
+
+fn slug(name: &str) -> String {
+    name.to_kebab_case()
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    name: String,
+    display_name: String,
+    description: &'static str,
+    version: &'static str,
+    publisher: &'static str,
+    engines: Engines,
+    categories: &'static [&'static str],
+    contributes: Contributes,
+}
+
+#[derive(Debug, Serialize)]
+struct Engines {
+    vscode: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Contributes {
+    themes: Vec<ThemeContribution>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThemeContribution {
+    label: String,
+    ui_theme: &'static str,
+    path: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyntarkTheme;
+
+    fn extension_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("syntinct-vscode-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn themes() -> Vec<VsCodeTheme> {
+        let base = SyntarkTheme::default();
+        vec![
+            VsCodeTheme::new("Syntark dark", &base),
+            VsCodeTheme::new("Syntark light", &base),
+        ]
+    }
+
+    #[test]
+    fn extension_can_be_regenerated() {
+        let dir = extension_dir("regenerate");
+        let paths = write_vscode_extension(&dir, "Syntark", &themes(), false).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(dir.join("themes/syntark-dark-color-theme.json").exists());
+        assert!(is_generated_manifest(&dir.join("package.json")).unwrap());
+        write_vscode_extension(&dir, "Syntark", &themes(), false).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn nothing_is_written_over_foreign_files() {
+        let dir = extension_dir("foreign");
+        write_vscode_extension(&dir, "Syntark", &themes(), false).unwrap();
+        let light = dir.join("themes/syntark-light-color-theme.json");
+        fs::write(&light, "{}\n").unwrap();
+        let dark = dir.join("themes/syntark-dark-color-theme.json");
+        fs::write(&dark, format!("// {GENERATED_MARKER}\n")).unwrap();
+        assert!(matches!(
+            write_vscode_extension(&dir, "Syntark", &themes(), false),
+            Err(OutputError::NotGenerated(path)) if path == light
+        ));
+        assert_eq!(
+            fs::read_to_string(&dark).unwrap(),
+            format!("// {GENERATED_MARKER}\n")
+        );
+        fs::write(&light, format!("// {GENERATED_MARKER}\n")).unwrap();
+        fs::write(dir.join("package.json"), "{\"name\": \"mine\"}\n").unwrap();
+        assert!(matches!(
+            write_vscode_extension(&dir, "Syntark", &themes(), false),
+            Err(OutputError::NotGenerated(path)) if path.ends_with("package.json")
+        ));
+        write_vscode_extension(&dir, "Syntark", &themes(), true).unwrap();
+        assert!(is_generated_manifest(&dir.join("package.json")).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{
+    color::CssColor, output::GENERATED_MARKER, to_oklch, AnsiColor, Category, DiagnosticLevel,
+    Style, Theme, Token,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+const SCHEMA: &str = "https://zed.dev/schema/themes/v0.2.0.json";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ZedTheme {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    name: String,
+    author: &'static str,
+    themes: Vec<ZedThemeVariant>,
+}
+
+impl ZedTheme {
+    pub fn new<S, D, L>(name: S, dark_base: &D, light_base: &L) -> Self
+    where
+        S: Into<String>,
+        D: Theme + ?Sized,
+        L: Theme + ?Sized,
+    {
+        let name = name.into();
+        Self {
+            schema: SCHEMA,
+            themes: vec![
+                ZedThemeVariant::new(format!("{name} Dark"), Appearance::Dark, dark_base),
+                ZedThemeVariant::new(format!("{name} Light"), Appearance::Light, light_base),
+            ],
+            name,
+            author: "syntinct",
+        }
+    }
+
+    pub fn single<S, T>(name: S, base: &T) -> Self
+    where
+        S: Into<String>,
+        T: Theme + ?Sized,
+    {
+        let name = name.into();
+        let appearance = if to_oklch(base.category_color(Category::NormalBackground)).l <= 0.5 {
+            Appearance::Dark
+        } else {
+            Appearance::Light
+        };
+        Self {
+            schema: SCHEMA,
+            themes: vec![ZedThemeVariant::new(name.clone(), appearance, base)],
+            name,
+            author: "syntinct",
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writeln!(writer, "// {GENERATED_MARKER}")?;
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Appearance {
+    Dark,
+    Light,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct ZedThemeVariant {
+    name: String,
+    appearance: Appearance,
+    style: ZedStyle,
+}
+
+impl ZedThemeVariant {
+    fn new<T>(name: String, appearance: Appearance, base: &T) -> Self
+    where
+        T: Theme + ?Sized,
+    {
+        let syntax = enum_iterator::all::<Token>()
+            .flat_map(|token| {
+                let style = SyntaxStyle::from(&base.token_style(token));
+                syntax_keys(token)
+                    .iter()
+                    .map(move |&key| (key, style.clone()))
+            })
+            .collect();
+        let player = Player {
+            cursor: CssColor(base.category_color(Category::TermCursor)).to_string(),
+            background: CssColor(base.category_color(Category::TermCursor)).to_string(),
+            selection: CssColor(base.category_color(Category::Selection)).to_string(),
+        };
+        Self {
+            name,
+            appearance,
+            style: ZedStyle {
+                colors: style_colors(base),
+                players: vec![player],
+                syntax,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct ZedStyle {
+    #[serde(flatten)]
+    colors: BTreeMap<&'static str, String>,
+    players: Vec<Player>,
+    syntax: BTreeMap<&'static str, SyntaxStyle>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct Player {
+    cursor: String,
+    background: String,
+    selection: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct SyntaxStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_style: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_weight: Option<u16>,
+}
+
+impl From<&Style> for SyntaxStyle {
+    fn from(style: &Style) -> Self {
+        Self {
+            color: style.foreground.map(|c| CssColor(c).to_string()),
+            background_color: style.background.map(|c| CssColor(c).to_string()),
+            font_style: style
+                .italic
+                .map(|italic| if italic { "italic" } else { "normal" }),
+            font_weight: style.bold.map(|bold| if bold { 700 } else { 400 }),
+        }
+    }
+}
+
+fn syntax_keys(token: Token) -> &'static [&'static str] {
+    match token {
+        Token::Attribute => &["attribute"],
+        Token::Boolean => &["boolean"],
+        Token::Character => &["string.special"],
+        Token::Comment => &["comment"],
+        Token::Constant => &["constant"],
+        Token::ConstGenericParameter => &[],
+        Token::Delimiter => &[
+            "punctuation",
+            "punctuation.bracket",
+            "punctuation.delimiter",
+        ],
+        Token::DocComment => &["comment.doc"],
+        Token::Enum => &["enum"],
+        Token::Field => &["property"],
+        Token::Float => &[],
+        Token::Function => &["function"],
+        Token::Identifier => &[],
+        Token::Integer => &["number"],
+        Token::Interface => &[],
+        Token::Keyword => &["keyword"],
+        Token::Link => &["link_text", "link_uri"],
+        Token::Macro => &["preproc"],
+        Token::Module => &["namespace"],
+        Token::Operator => &["operator"],
+        Token::Parameter => &["variable.parameter"],
+        Token::Static => &[],
+        Token::String => &["string"],
+        Token::Struct => &[],
+        Token::Tag => &["tag"],
+        Token::Todo => &[],
+        Token::Type => &["type"],
+        Token::TypeParameter => &[],
+        Token::Variable => &["variable"],
+        Token::Variant => &["variant", "constructor"],
+    }
+}
+
+fn style_colors<T: Theme + ?Sized>(base: &T) -> BTreeMap<&'static str, String> {
+    let categories = [
+        ("background", Category::NormalBackground),
+        ("border", Category::MessageSeparator),
+        ("border.variant", Category::ColumnGuide),
+        ("created.background", Category::DiffAdd),
+        ("deleted.background", Category::DiffDelete),
+        ("editor.active_line.background", Category::CursorLine),
+        ("editor.active_line_number", Category::CursorLineNumber),
+        ("editor.background", Category::NormalBackground),
+        ("editor.foreground", Category::Normal),
+        ("editor.gutter.background", Category::NormalBackground),
+        ("editor.invisible", Category::Whitespace),
+        ("editor.line_number", Category::LineNumber),
+        ("editor.wrap_guide", Category::ColumnGuide),
+        ("element.selected", Category::Selection),
+        ("elevated_surface.background", Category::StatusLine),
+        ("modified.background", Category::DiffChange),
+        ("panel.background", Category::StatusLine),
+        ("search.match_background", Category::SearchMatch),
+        ("status_bar.background", Category::StatusLine),
+        ("surface.background", Category::StatusLine),
+        ("tab.active_background", Category::ActiveTabBackground),
+        ("tab.inactive_background", Category::InactiveTabBackground),
+        ("tab_bar.background", Category::InactiveTabBackground),
+        ("terminal.background", Category::NormalBackground),
+        ("terminal.foreground", Category::Normal),
+        ("text", Category::Normal),
+        ("text.muted", Category::InactiveTab),
+        ("title_bar.background", Category::StatusLine),
+        ("toolbar.background", Category::NormalBackground),
+    ]
+    .map(|(key, category)| (key, base.category_color(category)));
+    let version_control = [
+        ("created", base.token_color(Token::String)),
+        (
+            "deleted",
+            base.diagnostic_level_color(DiagnosticLevel::Error),
+        ),
+        (
+            "modified",
+            base.diagnostic_level_color(DiagnosticLevel::Warning),
+        ),
+    ];
+    let diagnostics = [
+        ("error", DiagnosticLevel::Error),
+        ("hint", DiagnosticLevel::Hint),
+        ("info", DiagnosticLevel::Info),
+        ("warning", DiagnosticLevel::Warning),
+    ]
+    .map(|(key, level)| (key, base.diagnostic_level_color(level)));
+    let ansi = enum_iterator::all::<AnsiColor>().map(|color| {
+        let key = match color {
+            AnsiColor::Black => "terminal.ansi.black",
+            AnsiColor::Red => "terminal.ansi.red",
+            AnsiColor::Green => "terminal.ansi.green",
+            AnsiColor::Yellow => "terminal.ansi.yellow",
+            AnsiColor::Blue => "terminal.ansi.blue",
+            AnsiColor::Magenta => "terminal.ansi.magenta",
+            AnsiColor::Cyan => "terminal.ansi.cyan",
+            AnsiColor::White => "terminal.ansi.white",
+            AnsiColor::BrightBlack => "terminal.ansi.bright_black",
+            AnsiColor::BrightRed => "terminal.ansi.bright_red",
+            AnsiColor::BrightGreen => "terminal.ansi.bright_green",
+            AnsiColor::BrightYellow => "terminal.ansi.bright_yellow",
+            AnsiColor::BrightBlue => "terminal.ansi.bright_blue",
+            AnsiColor::BrightMagenta => "terminal.ansi.bright_magenta",
+            AnsiColor::BrightCyan => "terminal.ansi.bright_cyan",
+            AnsiColor::BrightWhite => "terminal.ansi.bright_white",
+        };
+        (key, base.ansi_color(color))
+    });
+    categories
+        .into_iter()
+        .chain(version_control)
+        .chain(diagnostics)
+        .chain(ansi)
+        .map(|(key, color)| (key, CssColor(color).to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Inverted, SyntarkTheme};
+
+    fn parse(theme: &ZedTheme) -> serde_json::Value {
+        let output = theme.to_json();
+        let (marker, json) = output.split_once('\n').unwrap();
+        assert_eq!(marker, format!("// {GENERATED_MARKER}"));
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn family_has_dark_and_light_themes() {
+        let dark = SyntarkTheme::default();
+        let light = Inverted::new(SyntarkTheme::default());
+        let json = parse(&ZedTheme::new("syntark", &dark, &light));
+        let themes = json["themes"].as_array().unwrap();
+        assert_eq!(themes[0]["name"], "syntark Dark");
+        assert_eq!(themes[0]["appearance"], "dark");
+        assert_eq!(themes[1]["name"], "syntark Light");
+        assert_eq!(themes[1]["appearance"], "light");
+        assert_eq!(
+            themes[1]["style"]["editor.background"],
+            CssColor(light.category_color(Category::NormalBackground)).to_string()
+        );
+    }
+
+    #[test]
+    fn single_theme_matches_its_background() {
+        let json = parse(&ZedTheme::single("syntark", &SyntarkTheme::default()));
+        let themes = json["themes"].as_array().unwrap();
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0]["name"], "syntark");
+        assert_eq!(themes[0]["appearance"], "dark");
+        let light = Inverted::new(SyntarkTheme::default());
+        let json = parse(&ZedTheme::single("syntark", &light));
+        assert_eq!(json["themes"][0]["appearance"], "light");
+    }
+
+    #[test]
+    fn version_control_text_is_readable_over_diff_tints() {
+        let base = SyntarkTheme::default();
+        let json = parse(&ZedTheme::single("syntark", &base));
+        let style = &json["themes"][0]["style"];
+        let background = base.category_color(Category::NormalBackground);
+        for (key, category) in [
+            ("created", Category::DiffAdd),
+            ("deleted", Category::DiffDelete),
+            ("modified", Category::DiffChange),
+        ] {
+            assert_eq!(
+                style[format!("{key}.background")],
+                CssColor(base.category_color(category)).to_string(),
+                "{key}"
+            );
+            let color = crate::parse_color(style[key].as_str().unwrap()).unwrap();
+            let ratio = crate::wcag_contrast_ratio(color, background);
+            assert!(ratio >= 3.0, "{key}: {ratio}");
+        }
+    }
+
+    #[test]
+    fn syntax_styles_carry_font_attributes() {
+        let base = SyntarkTheme::default();
+        let json = parse(&ZedTheme::single("syntark", &base));
+        let syntax = &json["themes"][0]["style"]["syntax"];
+        assert_eq!(syntax["keyword"]["font_weight"], 700);
+        assert_eq!(syntax["comment"]["font_style"], "italic");
+        assert_eq!(
+            syntax["string"],
+            serde_json::json!({ "color": CssColor(base.token_color(Token::String)).to_string() })
+        );
+    }
+}